@@ -1538,6 +1538,9 @@ fn substitute_macros(input: &str) -> String {
         ("[YANK]", "        Yank"),
         ("[OWNER]", "       Owner"),
         ("[MIGRATING]", "   Migrating"),
+        ("[SORTED]", "      Sorted"),
+        ("[UNCHANGED]", "   Unchanged"),
+        ("[CHECKED]", "     Checked"),
     ];
     let mut result = input.to_owned();
     for &(pat, subst) in &macros {
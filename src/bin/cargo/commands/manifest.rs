@@ -0,0 +1,52 @@
+use crate::command_prelude::*;
+use anyhow::format_err;
+use cargo::core::features;
+use cargo::ops;
+
+pub fn cli() -> App {
+    subcommand("manifest")
+        .about("Edit or inspect a Cargo.toml manifest")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            subcommand("sort-deps")
+                .about("Sort dependency tables alphabetically by name")
+                .arg_manifest_path(),
+        )
+        .subcommand(
+            subcommand("check-min-rust-version")
+                .about("Check that no dependency requires a newer `rust-version`")
+                .arg_manifest_path(),
+        )
+}
+
+pub fn exec(config: &mut Config, args: &ArgMatches<'_>) -> CliResult {
+    if !config.cli_unstable().unstable_options {
+        if config.nightly_features_allowed {
+            return Err(format_err!(
+                "the `cargo manifest` command is unstable, pass `-Z unstable-options` to enable it"
+            )
+            .into());
+        } else {
+            return Err(format_err!(
+                "the `cargo manifest` command is unstable, and only available on the \
+                 nightly channel of Cargo, but this is the `{}` channel\n\
+                 {}",
+                features::channel(),
+                features::SEE_CHANNELS
+            )
+            .into());
+        }
+    }
+    match args.subcommand() {
+        ("sort-deps", Some(args)) => {
+            let root = args.root_manifest(config)?;
+            ops::sort_deps(&root, config)?;
+        }
+        ("check-min-rust-version", Some(args)) => {
+            let ws = args.workspace(config)?;
+            ops::check_min_rust_version(&ws)?;
+        }
+        (cmd, _) => panic!("unexpected command `{}`", cmd),
+    }
+    Ok(())
+}
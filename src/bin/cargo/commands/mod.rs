@@ -16,6 +16,7 @@ pub fn builtin() -> Vec<App> {
         locate_project::cli(),
         login::cli(),
         logout::cli(),
+        manifest::cli(),
         metadata::cli(),
         new::cli(),
         owner::cli(),
@@ -54,6 +55,7 @@ pub fn builtin_exec(cmd: &str) -> Option<fn(&mut Config, &ArgMatches<'_>) -> Cli
         "locate-project" => locate_project::exec,
         "login" => login::exec,
         "logout" => logout::exec,
+        "manifest" => manifest::exec,
         "metadata" => metadata::exec,
         "new" => new::exec,
         "owner" => owner::exec,
@@ -93,6 +95,7 @@ pub mod install;
 pub mod locate_project;
 pub mod login;
 pub mod logout;
+pub mod manifest;
 pub mod metadata;
 pub mod new;
 pub mod owner;
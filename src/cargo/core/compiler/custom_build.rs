@@ -210,6 +210,26 @@ fn build_work(cx: &mut Context<'_, '_>, unit: &Unit) -> CargoResult<Job> {
         cmd.env("CARGO_MANIFEST_LINKS", links);
     }
 
+    // Pass along the variables declared in `package.env` explicitly so they
+    // show up in the build plan, and fail early if a required one is missing.
+    for (name, spec) in unit.pkg.manifest().env() {
+        match std::env::var_os(name) {
+            Some(value) => {
+                cmd.env(name, value);
+            }
+            None if spec.required == Some(true) => anyhow::bail!(
+                "environment variable `{}` is required by the build script of `{}`{}",
+                name,
+                unit.pkg,
+                spec.description
+                    .as_ref()
+                    .map(|d| format!(" ({})", d))
+                    .unwrap_or_default()
+            ),
+            None => {}
+        }
+    }
+
     // Be sure to pass along all enabled features for this package, this is the
     // last piece of statically known information that we have.
     for feat in &unit.features {
@@ -1481,8 +1481,18 @@ fn build_script_local_fingerprints(
     // obvious.
     let pkg_root = unit.pkg.root().to_path_buf();
     let target_dir = target_root(cx);
+    // Variables declared in `package.env` act as implicit
+    // `rerun-if-env-changed` hints on top of whatever the script prints.
+    let declared_env: Vec<String> = unit.pkg.manifest().env().keys().cloned().collect();
     let calculate =
         move |deps: &BuildDeps, pkg_fingerprint: Option<&dyn Fn() -> CargoResult<String>>| {
+            let declared = declared_env
+                .iter()
+                .filter(|var| !deps.rerun_if_env_changed.contains(var))
+                .map(|var| LocalFingerprint::RerunIfEnvChanged {
+                    var: var.clone(),
+                    val: env::var(var).ok(),
+                });
             if deps.rerun_if_changed.is_empty() && deps.rerun_if_env_changed.is_empty() {
                 match pkg_fingerprint {
                     // FIXME: this is somewhat buggy with respect to docker and
@@ -1501,7 +1511,9 @@ fn build_script_local_fingerprints(
                             "old local fingerprints deps {:?} precalculated={:?}",
                             pkg_root, s
                         );
-                        return Ok(Some(vec![LocalFingerprint::Precalculated(s)]));
+                        let mut local = vec![LocalFingerprint::Precalculated(s)];
+                        local.extend(declared);
+                        return Ok(Some(local));
                     }
                     None => return Ok(None),
                 }
@@ -1510,7 +1522,9 @@ fn build_script_local_fingerprints(
             // Ok so now we're in "new mode" where we can have files listed as
             // dependencies as well as env vars listed as dependencies. Process
             // them all here.
-            Ok(Some(local_fingerprints_deps(deps, &target_dir, &pkg_root)))
+            let mut local = local_fingerprints_deps(deps, &target_dir, &pkg_root);
+            local.extend(declared);
+            Ok(Some(local))
         };
 
     // Note that `false` == "not overridden"
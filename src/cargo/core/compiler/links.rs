@@ -23,11 +23,13 @@ pub fn validate_links(resolve: &Resolve, unit_graph: &UnitGraph) -> CargoResult<
         if !validated.insert(unit.pkg.package_id()) {
             continue;
         }
-        let lib = match unit.pkg.manifest().links() {
+        // The summary holds the key the library is unique under, which
+        // includes the `links-version` if one is set.
+        let lib = match unit.pkg.summary().links() {
             Some(lib) => lib,
             None => continue,
         };
-        if let Some(&prev) = links.get(lib) {
+        if let Some(&prev) = links.get(lib.as_str()) {
             let pkg = unit.pkg.package_id();
 
             let describe_path = |pkgid: PackageId| -> String {
@@ -761,6 +761,7 @@ fn build_base_args(
         ref panic,
         incremental,
         strip,
+        self_profile,
         ..
     } = unit.profile;
     let test = unit.mode.is_any_test();
@@ -913,6 +914,15 @@ fn build_base_args(
         cmd.arg("-Z").arg(format!("strip={}", strip));
     }
 
+    if self_profile {
+        let dir = bcx.ws.target_dir().join("profile-data");
+        let dir = dir.as_path_unlocked();
+        paths::create_dir_all(dir)?;
+        let mut arg = OsString::from("self-profile=");
+        arg.push(dir);
+        cmd.arg("-Z").arg(arg);
+    }
+
     if unit.is_std {
         // -Zforce-unstable-if-unmarked prevents the accidental use of
         // unstable crates within the sysroot (such as "extern crate libc" or
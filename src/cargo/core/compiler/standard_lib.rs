@@ -66,6 +66,7 @@ pub fn resolve_std<'cfg>(
         /*default_members*/ &None,
         /*exclude*/ &None,
         /*custom_metadata*/ &None,
+        /*limits*/ &None,
     ));
     let virtual_manifest = crate::core::VirtualManifest::new(
         /*replace*/ Vec::new(),
@@ -74,6 +75,7 @@ pub fn resolve_std<'cfg>(
         /*profiles*/ None,
         crate::core::Features::default(),
         None,
+        crate::core::resolver::ResolverSource::Absent,
     );
 
     let config = ws.config();
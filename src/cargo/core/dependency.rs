@@ -1,3 +1,4 @@
+use anyhow::bail;
 use cargo_platform::Platform;
 use log::trace;
 use semver::ReqParseError;
@@ -45,6 +46,14 @@ struct Inner {
     // This dependency should be used only for this platform.
     // `None` means *all platforms*.
     platform: Option<Platform>,
+
+    // The artifacts requested from this dependency, if it is an artifact
+    // dependency.
+    artifact: Option<Artifact>,
+
+    // The checksum the package must have, see the `dep-checksum` unstable
+    // feature.
+    checksum: Option<InternedString>,
 }
 
 #[derive(Serialize)]
@@ -56,6 +65,9 @@ struct SerializedDependency<'a> {
     rename: Option<&'a str>,
 
     optional: bool,
+    /// Whether this is a public dependency, see the `public-dependency`
+    /// unstable feature.
+    public: bool,
     uses_default_features: bool,
     features: &'a [InternedString],
     target: Option<&'a Platform>,
@@ -80,6 +92,7 @@ impl ser::Serialize for Dependency {
             req: self.version_req().to_string(),
             kind: self.kind(),
             optional: self.is_optional(),
+            public: self.is_public(),
             uses_default_features: self.uses_default_features(),
             features: self.features(),
             target: self.platform(),
@@ -91,6 +104,88 @@ impl ser::Serialize for Dependency {
     }
 }
 
+/// The artifacts requested by an artifact dependency, see the
+/// `artifact-dependencies` unstable feature.
+#[derive(PartialEq, Eq, Hash, Ord, PartialOrd, Clone, Debug)]
+pub struct Artifact {
+    kinds: Vec<ArtifactKind>,
+    is_lib: bool,
+    target: Option<InternedString>,
+}
+
+impl Artifact {
+    pub fn parse(
+        kinds: &[impl AsRef<str>],
+        is_lib: bool,
+        target: Option<&str>,
+    ) -> CargoResult<Self> {
+        if kinds.is_empty() {
+            bail!("`artifact` must list at least one artifact kind");
+        }
+        let mut parsed = Vec::new();
+        for kind in kinds {
+            let kind = ArtifactKind::parse(kind.as_ref())?;
+            if !parsed.contains(&kind) {
+                parsed.push(kind);
+            }
+        }
+        if is_lib && parsed.contains(&ArtifactKind::Bin) {
+            bail!("`lib = true` cannot be combined with the `bin` artifact kind");
+        }
+        Ok(Artifact {
+            kinds: parsed,
+            is_lib,
+            target: target.map(InternedString::new),
+        })
+    }
+
+    pub fn kinds(&self) -> &[ArtifactKind] {
+        &self.kinds
+    }
+
+    /// Whether the library of the dependency is also available to the
+    /// dependent, as with a regular dependency.
+    pub fn is_lib(&self) -> bool {
+        self.is_lib
+    }
+
+    /// The target triple the artifacts are built for, if different from
+    /// the target of the dependent.
+    pub fn target(&self) -> Option<InternedString> {
+        self.target
+    }
+}
+
+/// A kind of artifact that an artifact dependency can request.
+#[derive(PartialEq, Eq, Hash, Ord, PartialOrd, Clone, Copy, Debug)]
+pub enum ArtifactKind {
+    Bin,
+    Cdylib,
+    Staticlib,
+}
+
+impl ArtifactKind {
+    pub fn parse(kind: &str) -> CargoResult<Self> {
+        Ok(match kind {
+            "bin" => ArtifactKind::Bin,
+            "cdylib" => ArtifactKind::Cdylib,
+            "staticlib" => ArtifactKind::Staticlib,
+            _ => bail!(
+                "`{}` is not a valid artifact kind, expected one of `bin`, `cdylib` or `staticlib`",
+                kind
+            ),
+        })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArtifactKind::Bin => "bin",
+            ArtifactKind::Cdylib => "cdylib",
+            ArtifactKind::Staticlib => "staticlib",
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Ord, PartialOrd, Clone, Debug, Copy)]
 pub enum DepKind {
     Normal,
@@ -222,6 +317,8 @@ impl Dependency {
                 default_features: true,
                 specified_req: false,
                 platform: None,
+                artifact: None,
+                checksum: None,
                 explicit_name_in_toml: None,
             }),
         }
@@ -373,6 +470,30 @@ impl Dependency {
         self
     }
 
+    /// Sets the artifacts requested from this dependency.
+    pub fn set_artifact(&mut self, artifact: Artifact) -> &mut Dependency {
+        Rc::make_mut(&mut self.inner).artifact = Some(artifact);
+        self
+    }
+
+    /// The artifacts requested from this dependency, if it is an artifact
+    /// dependency.
+    pub fn artifact(&self) -> Option<&Artifact> {
+        self.inner.artifact.as_ref()
+    }
+
+    /// Sets the checksum that the package fulfilling this dependency must
+    /// have.
+    pub fn set_checksum(&mut self, checksum: Option<InternedString>) -> &mut Dependency {
+        Rc::make_mut(&mut self.inner).checksum = checksum;
+        self
+    }
+
+    /// The checksum pinned for this dependency in the manifest, if any.
+    pub fn checksum(&self) -> Option<InternedString> {
+        self.inner.checksum
+    }
+
     pub fn set_explicit_name_in_toml(
         &mut self,
         name: impl Into<InternedString>,
@@ -438,6 +559,10 @@ impl Dependency {
     /// Returns `true` if the package (`sum`) can fulfill this dependency request.
     pub fn matches(&self, sum: &Summary) -> bool {
         self.matches_id(sum.package_id())
+            && self
+                .inner
+                .checksum
+                .map_or(true, |checksum| sum.checksum() == Some(checksum.as_str()))
     }
 
     /// Returns `true` if the package (`id`) can fulfill this dependency request.
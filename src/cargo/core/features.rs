@@ -388,6 +388,51 @@ features! {
 
     // Support for 2021 edition.
     (unstable, edition2021, "", "reference/unstable.html#edition-2021"),
+
+    // Opting a package out of workspace root discovery.
+    (unstable, standalone_package, "", "reference/unstable.html#standalone-package"),
+
+    // Remapping of source paths in compiler output.
+    (unstable, trim_paths, "", "reference/unstable.html#profile-trim-paths-option"),
+
+    // Declaring the environment variables a build script reads.
+    (unstable, package_env, "", "reference/unstable.html#package-env"),
+
+    // The `[lints]` table.
+    (unstable, lints, "", "reference/unstable.html#lints"),
+
+    // The `package-defaults` opt-level shorthand in profiles.
+    (unstable, profile_package_defaults, "", "reference/unstable.html#profile-package-defaults"),
+
+    // Allow depending on the binary and C library artifacts of a package.
+    (unstable, artifact_dependencies, "", "reference/unstable.html#artifact-dependencies"),
+
+    // Allow running rustc with `-Z self-profile` from a profile.
+    (unstable, profile_self_profile, "", "reference/unstable.html#profile-self-profile-option"),
+
+    // Structured funding metadata in `[package.funding]`.
+    (unstable, package_funding, "", "reference/unstable.html#package-funding"),
+
+    // Declaring the lowest ABI version a dependency must provide.
+    (unstable, abi_version, "", "reference/unstable.html#abi-version"),
+
+    // Budgets for the size of member manifests in `[workspace.limits]`.
+    (unstable, workspace_limits, "", "reference/unstable.html#workspace-limits"),
+
+    // Pinning the checksum of a registry dependency in the manifest.
+    (unstable, dep_checksum, "", "reference/unstable.html#dep-checksum"),
+
+    // Leaving internal metadata out of published manifests.
+    (unstable, publish_strip_metadata, "", "reference/unstable.html#publish-strip-metadata"),
+
+    // Linking several major versions of a native library with `links-version`.
+    (unstable, links_version, "", "reference/unstable.html#links-version"),
+
+    // Writing a dependency `version` as a list of alternative requirements.
+    (unstable, version_list, "", "reference/unstable.html#version-list"),
+
+    // Publishing a `git` dependency with a different `registry-version`.
+    (unstable, registry_version, "", "reference/unstable.html#registry-version"),
 }
 
 const PUBLISH_LOCKFILE_REMOVED: &str = "The publish-lockfile key in Cargo.toml \
@@ -558,6 +603,8 @@ pub struct CliUnstable {
     pub extra_link_arg: bool,
     pub credential_process: bool,
     pub configurable_env: bool,
+    pub strict_patch_versions: bool,
+    pub strict_toml: bool,
 }
 
 const STABILIZED_COMPILE_PROGRESS: &str = "The progress bar is now always \
@@ -747,6 +794,8 @@ impl CliUnstable {
             "weak-dep-features" => self.weak_dep_features = parse_empty(k, v)?,
             "extra-link-arg" => self.extra_link_arg = parse_empty(k, v)?,
             "credential-process" => self.credential_process = parse_empty(k, v)?,
+            "strict-patch-versions" => self.strict_patch_versions = parse_empty(k, v)?,
+            "strict-toml" => self.strict_toml = parse_empty(k, v)?,
             "compile-progress" => stabilized_warn(k, "1.30", STABILIZED_COMPILE_PROGRESS),
             "offline" => stabilized_err(k, "1.36", STABILIZED_OFFLINE)?,
             "cache-messages" => stabilized_warn(k, "1.40", STABILIZED_CACHE_MESSAGES),
@@ -11,12 +11,12 @@ use serde::Serialize;
 use url::Url;
 
 use crate::core::compiler::CrateType;
-use crate::core::resolver::ResolveBehavior;
+use crate::core::resolver::{ResolveBehavior, ResolverSource};
 use crate::core::{Dependency, PackageId, PackageIdSpec, SourceId, Summary};
 use crate::core::{Edition, Feature, Features, WorkspaceConfig};
 use crate::util::errors::*;
 use crate::util::interning::InternedString;
-use crate::util::toml::{TomlManifest, TomlProfiles};
+use crate::util::toml::{TomlEnvSpec, TomlFunding, TomlManifest, TomlProfiles};
 use crate::util::{short_hash, Config, Filesystem};
 
 pub enum EitherManifest {
@@ -49,7 +49,11 @@ pub struct Manifest {
     im_a_teapot: Option<bool>,
     default_run: Option<String>,
     metabuild: Option<Vec<String>>,
+    env: BTreeMap<String, TomlEnvSpec>,
     resolve_behavior: Option<ResolveBehavior>,
+    resolver_source: ResolverSource,
+    deprecated_syntax: DeprecatedSyntax,
+    contents_hash: u64,
 }
 
 /// When parsing `Cargo.toml`, some warnings should silenced
@@ -59,9 +63,70 @@ pub struct Manifest {
 pub struct DelayedWarning {
     pub message: String,
     pub is_critical: bool,
+    pub category: WarningCategory,
+    /// Whether the warning was already printed when the manifest was read,
+    /// so that it is only recorded here and not printed again.
+    pub emitted: bool,
 }
 
-#[derive(Clone, Debug)]
+/// What a manifest warning is about, so that warnings can be grouped when
+/// they are presented.
+///
+/// The variants are declared in the order used by [`Warnings::by_category`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarningCategory {
+    /// Deprecated or stabilized syntax and cargo features.
+    Deprecated,
+    /// Keys that Cargo doesn't recognize.
+    UnusedKey,
+    /// Problems with a dependency declaration.
+    Dependency,
+    /// Problems with the `[features]` table.
+    Feature,
+    /// Problems with build targets.
+    Target,
+    /// Problems with profiles.
+    Profile,
+    /// Problems with package metadata, like its name or license.
+    Metadata,
+    /// Warnings that weren't given a category.
+    Other,
+}
+
+impl WarningCategory {
+    pub const ALL: [WarningCategory; 8] = [
+        WarningCategory::Deprecated,
+        WarningCategory::UnusedKey,
+        WarningCategory::Dependency,
+        WarningCategory::Feature,
+        WarningCategory::Target,
+        WarningCategory::Profile,
+        WarningCategory::Metadata,
+        WarningCategory::Other,
+    ];
+
+    /// A stable identifier for the category.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WarningCategory::Deprecated => "deprecated",
+            WarningCategory::UnusedKey => "unused-key",
+            WarningCategory::Dependency => "dependency",
+            WarningCategory::Feature => "feature",
+            WarningCategory::Target => "target",
+            WarningCategory::Profile => "profile",
+            WarningCategory::Metadata => "metadata",
+            WarningCategory::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for WarningCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct Warnings(Vec<DelayedWarning>);
 
 #[derive(Clone, Debug)]
@@ -73,6 +138,8 @@ pub struct VirtualManifest {
     warnings: Warnings,
     features: Features,
     resolve_behavior: Option<ResolveBehavior>,
+    resolver_source: ResolverSource,
+    contents_hash: u64,
 }
 
 /// General metadata about a package which is just blindly uploaded to the
@@ -97,6 +164,7 @@ pub struct ManifestMetadata {
     pub documentation: Option<String>, // URL
     pub badges: BTreeMap<String, BTreeMap<String, String>>,
     pub links: Option<String>,
+    pub funding: Option<TomlFunding>,
 }
 
 #[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -383,7 +451,9 @@ impl Manifest {
         default_run: Option<String>,
         original: Rc<TomlManifest>,
         metabuild: Option<Vec<String>>,
+        env: BTreeMap<String, TomlEnvSpec>,
         resolve_behavior: Option<ResolveBehavior>,
+        resolver_source: ResolverSource,
     ) -> Manifest {
         Manifest {
             summary,
@@ -406,7 +476,11 @@ impl Manifest {
             im_a_teapot,
             default_run,
             metabuild,
+            env,
             resolve_behavior,
+            resolver_source,
+            deprecated_syntax: DeprecatedSyntax::default(),
+            contents_hash: 0,
         }
     }
 
@@ -485,6 +559,33 @@ impl Manifest {
         self.resolve_behavior
     }
 
+    /// Which part of the manifest, if any, selected the resolver.
+    ///
+    /// Unlike [`resolve_behavior`](Self::resolve_behavior), this also
+    /// reports when the edition implies a resolver.
+    pub fn resolver_source(&self) -> ResolverSource {
+        self.resolver_source
+    }
+
+    /// A hash of the raw bytes of the `Cargo.toml` this manifest was read
+    /// from, see [`crate::util::toml::manifest_contents_hash`].
+    pub fn contents_hash(&self) -> u64 {
+        self.contents_hash
+    }
+
+    pub fn set_contents_hash(&mut self, hash: u64) {
+        self.contents_hash = hash;
+    }
+
+    /// Deprecated syntax that was found while parsing this manifest.
+    pub fn deprecated_syntax(&self) -> DeprecatedSyntax {
+        self.deprecated_syntax
+    }
+
+    pub fn deprecated_syntax_mut(&mut self) -> &mut DeprecatedSyntax {
+        &mut self.deprecated_syntax
+    }
+
     pub fn map_source(self, to_replace: SourceId, replace_with: SourceId) -> Manifest {
         Manifest {
             summary: self.summary.map_source(to_replace, replace_with),
@@ -536,6 +637,11 @@ impl Manifest {
         self.metabuild.as_ref()
     }
 
+    /// Environment variables the build script declares in `package.env`.
+    pub fn env(&self) -> &BTreeMap<String, TomlEnvSpec> {
+        &self.env
+    }
+
     pub fn metabuild_path(&self, target_dir: Filesystem) -> PathBuf {
         let hash = short_hash(&self.package_id());
         target_dir
@@ -553,6 +659,7 @@ impl VirtualManifest {
         profiles: Option<TomlProfiles>,
         features: Features,
         resolve_behavior: Option<ResolveBehavior>,
+        resolver_source: ResolverSource,
     ) -> VirtualManifest {
         VirtualManifest {
             replace,
@@ -562,6 +669,8 @@ impl VirtualManifest {
             warnings: Warnings::new(),
             features,
             resolve_behavior,
+            resolver_source,
+            contents_hash: 0,
         }
     }
 
@@ -589,6 +698,16 @@ impl VirtualManifest {
         &self.warnings
     }
 
+    /// A hash of the raw bytes of the `Cargo.toml` this manifest was read
+    /// from, see [`crate::util::toml::manifest_contents_hash`].
+    pub fn contents_hash(&self) -> u64 {
+        self.contents_hash
+    }
+
+    pub fn set_contents_hash(&mut self, hash: u64) {
+        self.contents_hash = hash;
+    }
+
     pub fn unstable_features(&self) -> &Features {
         &self.features
     }
@@ -599,6 +718,13 @@ impl VirtualManifest {
     pub fn resolve_behavior(&self) -> Option<ResolveBehavior> {
         self.resolve_behavior
     }
+
+    /// Which part of the manifest, if any, selected the resolver.
+    ///
+    /// This is either `[workspace]` or absent for virtual manifests.
+    pub fn resolver_source(&self) -> ResolverSource {
+        self.resolver_source
+    }
 }
 
 impl Target {
@@ -926,25 +1052,160 @@ impl fmt::Display for Target {
 }
 
 impl Warnings {
-    fn new() -> Warnings {
+    pub fn new() -> Warnings {
         Warnings(Vec::new())
     }
 
     pub fn add_warning(&mut self, s: String) {
-        self.0.push(DelayedWarning {
-            message: s,
+        self.push(WarningCategory::Other, s)
+    }
+
+    pub fn add_critical_warning(&mut self, s: String) {
+        self.push_critical(WarningCategory::Other, s)
+    }
+
+    /// Adds a warning, unless one with the same category and message was
+    /// already added.
+    pub fn push(&mut self, category: WarningCategory, message: String) {
+        self.insert(DelayedWarning {
+            message,
             is_critical: false,
+            category,
+            emitted: false,
         })
     }
 
-    pub fn add_critical_warning(&mut self, s: String) {
-        self.0.push(DelayedWarning {
-            message: s,
+    /// Records a warning that was already printed.
+    pub fn push_emitted(&mut self, category: WarningCategory, message: String) {
+        self.insert(DelayedWarning {
+            message,
+            is_critical: false,
+            category,
+            emitted: true,
+        })
+    }
+
+    /// Adds a warning that is reported as an error for workspace members.
+    pub fn push_critical(&mut self, category: WarningCategory, message: String) {
+        self.insert(DelayedWarning {
+            message,
             is_critical: true,
+            category,
+            emitted: false,
         })
     }
 
+    /// Adds each of `messages` as a warning in `category`.
+    pub fn extend(&mut self, category: WarningCategory, messages: Vec<String>) {
+        for message in messages {
+            self.push(category, message);
+        }
+    }
+
+    /// Moves all warnings of `other` to the end of this list.
+    pub fn append(&mut self, other: Warnings) {
+        for warning in other.0 {
+            self.insert(warning);
+        }
+    }
+
+    fn insert(&mut self, warning: DelayedWarning) {
+        let key = warning.dedup_key();
+        if !self.0.iter().any(|w| w.dedup_key() == key) {
+            self.0.push(warning);
+        }
+    }
+
     pub fn warnings(&self) -> &[DelayedWarning] {
         &self.0
     }
+
+    /// Returns the warnings grouped by category, in the order of
+    /// [`WarningCategory::ALL`]. Categories without warnings are left out,
+    /// and warnings keep the order they were added in within each group.
+    pub fn by_category(&self) -> Vec<(WarningCategory, Vec<&DelayedWarning>)> {
+        WarningCategory::ALL
+            .iter()
+            .map(|&category| {
+                let warnings = self.0.iter().filter(|w| w.category == category).collect();
+                (category, warnings)
+            })
+            .filter(|(_, warnings): &(_, Vec<_>)| !warnings.is_empty())
+            .collect()
+    }
+}
+
+impl DelayedWarning {
+    /// Two warnings with the same key are duplicates of each other.
+    pub fn dedup_key(&self) -> (WarningCategory, &str) {
+        (self.category, &self.message)
+    }
+}
+
+/// A set of deprecated manifest constructs, recorded while parsing a
+/// `Cargo.toml` so that tools can report on them without re-parsing.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct DeprecatedSyntax(u8);
+
+impl DeprecatedSyntax {
+    /// The file only parses with the TOML quirks accepted by older Cargo
+    /// versions, like a table header without a trailing newline.
+    pub const LEGACY_TOML: DeprecatedSyntax = DeprecatedSyntax(1 << 0);
+    /// The package is declared in a `[project]` table instead of `[package]`.
+    pub const PROJECT_TABLE: DeprecatedSyntax = DeprecatedSyntax(1 << 1);
+    /// A key is spelled with underscores, like `dev_dependencies`,
+    /// `default_features` or `crate_type`.
+    pub const UNDERSCORE_KEYS: DeprecatedSyntax = DeprecatedSyntax(1 << 2);
+    /// The manifest has a `[replace]` table.
+    pub const REPLACE: DeprecatedSyntax = DeprecatedSyntax(1 << 3);
+    /// The manifest has badges other than `maintenance`.
+    pub const BADGES: DeprecatedSyntax = DeprecatedSyntax(1 << 4);
+    /// The library sets `plugin = true`.
+    pub const PLUGIN: DeprecatedSyntax = DeprecatedSyntax(1 << 5);
+
+    const NAMES: &'static [(DeprecatedSyntax, &'static str)] = &[
+        (DeprecatedSyntax::LEGACY_TOML, "legacy-toml"),
+        (DeprecatedSyntax::PROJECT_TABLE, "project-table"),
+        (DeprecatedSyntax::UNDERSCORE_KEYS, "underscore-keys"),
+        (DeprecatedSyntax::REPLACE, "replace"),
+        (DeprecatedSyntax::BADGES, "badges"),
+        (DeprecatedSyntax::PLUGIN, "plugin"),
+    ];
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, other: DeprecatedSyntax) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: DeprecatedSyntax) {
+        self.0 |= other.0;
+    }
+
+    /// Returns the names of the constructs in this set, in a stable order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        DeprecatedSyntax::NAMES
+            .iter()
+            .filter(move |(flag, _)| self.contains(*flag))
+            .map(|(_, name)| *name)
+    }
+}
+
+impl std::ops::BitOr for DeprecatedSyntax {
+    type Output = DeprecatedSyntax;
+
+    fn bitor(self, other: DeprecatedSyntax) -> DeprecatedSyntax {
+        DeprecatedSyntax(self.0 | other.0)
+    }
+}
+
+impl ser::Serialize for DeprecatedSyntax {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        s.collect_seq(self.names())
+    }
 }
@@ -1,6 +1,6 @@
 pub use self::dependency::Dependency;
 pub use self::features::{CliUnstable, Edition, Feature, Features};
-pub use self::manifest::{EitherManifest, VirtualManifest};
+pub use self::manifest::{DeprecatedSyntax, EitherManifest, VirtualManifest, WarningCategory};
 pub use self::manifest::{Manifest, Target, TargetKind};
 pub use self::package::{Package, PackageSet};
 pub use self::package_id::PackageId;
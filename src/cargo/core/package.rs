@@ -20,15 +20,16 @@ use serde::Serialize;
 use crate::core::compiler::{CompileKind, RustcTargetData};
 use crate::core::dependency::DepKind;
 use crate::core::resolver::features::ForceAllTargets;
-use crate::core::resolver::{HasDevUnits, Resolve};
+use crate::core::resolver::{HasDevUnits, Resolve, ResolverSource};
 use crate::core::source::MaybePackage;
-use crate::core::{Dependency, Manifest, PackageId, SourceId, Target};
+use crate::core::{Dependency, DeprecatedSyntax, Manifest, PackageId, SourceId, Target};
 use crate::core::{SourceMap, Summary, Workspace};
 use crate::ops;
 use crate::util::config::PackageCacheLock;
 use crate::util::errors::{CargoResult, CargoResultExt, HttpNot200};
 use crate::util::interning::InternedString;
 use crate::util::network::Retry;
+use crate::util::toml::TomlFunding;
 use crate::util::{self, internal, Config, Progress, ProgressStyle};
 
 pub const MANIFEST_PREAMBLE: &str = "\
@@ -102,6 +103,12 @@ pub struct SerializedPackage {
     links: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     metabuild: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "DeprecatedSyntax::is_empty")]
+    deprecated_syntax: DeprecatedSyntax,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    funding: Option<TomlFunding>,
+    #[serde(skip_serializing_if = "ResolverSource::is_absent")]
+    resolver_source: ResolverSource,
 }
 
 impl Package {
@@ -263,6 +270,9 @@ impl Package {
             links: self.manifest().links().map(|s| s.to_owned()),
             metabuild: self.manifest().metabuild().cloned(),
             publish: self.publish().as_ref().cloned(),
+            deprecated_syntax: self.manifest().deprecated_syntax(),
+            funding: manmeta.funding.clone(),
+            resolver_source: self.manifest().resolver_source(),
         }
     }
 }
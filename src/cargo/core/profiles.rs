@@ -214,6 +214,12 @@ impl Profiles {
                     name
                 );
             }
+            if profile.merge_policy.is_some() {
+                bail!(
+                    "`merge-policy` must not be specified in root profile `{}`",
+                    name
+                );
+            }
             // Already inserted from `add_root_profiles`, no need to do anything.
             return Ok(());
         }
@@ -275,7 +281,7 @@ impl Profiles {
             }
         };
         match &mut maker.toml {
-            Some(toml) => toml.merge(profile),
+            Some(toml) => toml.merge_inheriting(profile),
             None => maker.toml = Some(profile.clone()),
         };
         Ok(maker)
@@ -510,6 +516,13 @@ fn merge_toml_overrides(
             merge_profile(profile, build_override);
         }
     }
+    if !is_member {
+        // An explicit `package."*"` table takes precedence over the
+        // `package-defaults` shorthand.
+        if let Some(opt_level) = &toml.package_defaults {
+            profile.opt_level = InternedString::new(&opt_level.level);
+        }
+    }
     if let Some(overrides) = toml.package.as_ref() {
         if !is_member {
             if let Some(all) = overrides.get(&ProfilePackageSpec::All) {
@@ -548,7 +561,7 @@ fn merge_toml_overrides(
 /// Does not merge overrides (see `merge_toml_overrides`).
 fn merge_profile(profile: &mut Profile, toml: &TomlProfile) {
     if let Some(ref opt_level) = toml.opt_level {
-        profile.opt_level = InternedString::new(&opt_level.0);
+        profile.opt_level = InternedString::new(&opt_level.level);
     }
     match toml.lto {
         Some(StringOrBool::Bool(b)) => profile.lto = Lto::Bool(b),
@@ -588,6 +601,9 @@ fn merge_profile(profile: &mut Profile, toml: &TomlProfile) {
     if let Some(incremental) = toml.incremental {
         profile.incremental = incremental;
     }
+    if let Some(self_profile) = toml.self_profile {
+        profile.self_profile = self_profile;
+    }
     profile.strip = match toml.strip {
         Some(StringOrBool::Bool(true)) => Strip::Named(InternedString::new("symbols")),
         None | Some(StringOrBool::Bool(false)) => Strip::None,
@@ -626,6 +642,7 @@ pub struct Profile {
     pub incremental: bool,
     pub panic: PanicStrategy,
     pub strip: Strip,
+    pub self_profile: bool,
 }
 
 impl Default for Profile {
@@ -644,6 +661,7 @@ impl Default for Profile {
             incremental: false,
             panic: PanicStrategy::Unwind,
             strip: Strip::None,
+            self_profile: false,
         }
     }
 }
@@ -670,6 +688,7 @@ compact_debug! {
                 incremental
                 panic
                 strip
+                self_profile
             )]
         }
     }
@@ -757,6 +776,7 @@ impl Profile {
             self.incremental,
             self.panic,
             self.strip,
+            self.self_profile,
         )
     }
 }
@@ -214,6 +214,7 @@ pub(super) fn activation_error(
     let all_req = semver::VersionReq::parse("*").unwrap();
     let mut new_dep = dep.clone();
     new_dep.set_version_req(all_req);
+    new_dep.set_checksum(None);
     let mut candidates = match registry.query_vec(&new_dep, false) {
         Ok(candidates) => candidates,
         Err(e) => return to_resolve_err(e),
@@ -245,6 +246,12 @@ pub(super) fn activation_error(
                 versions,
                 registry.describe_source(dep.source_id()),
             );
+            if let Some(checksum) = dep.checksum() {
+                msg.push_str(&format!(
+                    "the manifest also requires the checksum `{}`\n",
+                    checksum
+                ));
+            }
             msg.push_str("required by ");
             msg.push_str(&describe_path(
                 &cx.parents.path_to_bottom(&parent.package_id()),
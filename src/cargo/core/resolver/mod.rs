@@ -71,7 +71,7 @@ pub use self::encode::{EncodableDependency, EncodablePackageId, EncodableResolve
 pub use self::errors::{ActivateError, ActivateResult, ResolveError};
 pub use self::features::{ForceAllTargets, HasDevUnits};
 pub use self::resolve::{Resolve, ResolveVersion};
-pub use self::types::{ResolveBehavior, ResolveOpts};
+pub use self::types::{ResolveBehavior, ResolveOpts, ResolverSource};
 
 mod conflict_cache;
 mod context;
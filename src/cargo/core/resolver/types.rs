@@ -3,6 +3,7 @@ use crate::core::{Dependency, PackageId, Summary};
 use crate::util::errors::CargoResult;
 use crate::util::interning::InternedString;
 use crate::util::Config;
+use serde::Serialize;
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet};
 use std::ops::Range;
@@ -127,6 +128,36 @@ impl ResolveBehavior {
     }
 }
 
+/// Where the [`ResolveBehavior`] in effect for a manifest was decided.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResolverSource {
+    /// The `resolver` field in `[package]`.
+    PackageField,
+    /// The `resolver` field in `[workspace]`.
+    WorkspaceField,
+    /// No `resolver` field, but the edition implies the V2 resolver.
+    EditionDefault,
+    /// Nothing selected a resolver, so the V1 default is used.
+    Absent,
+}
+
+impl ResolverSource {
+    pub fn is_absent(&self) -> bool {
+        *self == ResolverSource::Absent
+    }
+
+    /// A short human-readable description, used in verbose output.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            ResolverSource::PackageField => "the `resolver` field in `[package]`",
+            ResolverSource::WorkspaceField => "the `resolver` field in `[workspace]`",
+            ResolverSource::EditionDefault => "the default for the package's edition",
+            ResolverSource::Absent => "the default",
+        }
+    }
+}
+
 /// Options for how the resolve should work.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct ResolveOpts {
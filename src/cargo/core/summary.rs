@@ -316,7 +316,26 @@ fn build_feature_map(
                         );
                     }
                 }
-                DepFeature { dep_name, weak, .. } => {
+                DepFeature {
+                    dep_name,
+                    dep_feature,
+                    weak,
+                    ..
+                } => {
+                    // Feature lists copied from another crate sometimes name
+                    // this package as if it were one of its dependencies.
+                    let normalize = |name: &str| name.replace('-', "_");
+                    if !is_any_dep && normalize(dep_name) == normalize(&pkg_id.name()) {
+                        bail!(
+                            "feature `{}` includes `{}`, but `{}` is the name of this package\n\
+                             To enable the `{}` feature of this package, write `\"{}\"` instead.",
+                            feature,
+                            fv,
+                            dep_name,
+                            dep_feature,
+                            dep_feature
+                        );
+                    }
                     // Validation of the feature name will be performed in the resolver.
                     if !is_any_dep {
                         bail!(
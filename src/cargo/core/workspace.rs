@@ -5,14 +5,15 @@ use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::slice;
 
-use glob::glob;
+use glob::{glob, MatchOptions, Pattern};
 use log::debug;
 use url::Url;
 
+use crate::core::dependency::DepKind;
 use crate::core::features::Features;
 use crate::core::registry::PackageRegistry;
 use crate::core::resolver::features::RequestedFeatures;
-use crate::core::resolver::ResolveBehavior;
+use crate::core::resolver::{ResolveBehavior, ResolverSource};
 use crate::core::{Dependency, Edition, PackageId, PackageIdSpec};
 use crate::core::{EitherManifest, Package, SourceId, VirtualManifest};
 use crate::ops;
@@ -20,9 +21,13 @@ use crate::sources::PathSource;
 use crate::util::errors::{CargoResult, CargoResultExt, ManifestError};
 use crate::util::interning::InternedString;
 use crate::util::paths;
-use crate::util::toml::{read_manifest, TomlProfiles};
+use crate::util::toml::{read_manifest, TomlProfiles, TomlWorkspaceLimits};
 use crate::util::{Config, Filesystem};
 
+/// The number of `package.workspace` pointers that are followed while
+/// looking for the root of a workspace, before giving up.
+const MAX_WORKSPACE_POINTER_HOPS: usize = 10;
+
 /// The core abstraction in Cargo for working with a workspace of crates.
 ///
 /// A workspace is often created very early on and then threaded through all
@@ -90,6 +95,9 @@ pub struct Workspace<'cfg> {
     /// The resolver behavior specified with the `resolver` field.
     resolve_behavior: ResolveBehavior,
 
+    /// Where `resolve_behavior` was decided, see `Manifest::resolver_source`.
+    resolver_source: ResolverSource,
+
     /// Workspace-level custom metadata
     custom_metadata: Option<toml::Value>,
 }
@@ -118,6 +126,10 @@ pub enum WorkspaceConfig {
     /// Indicates that `[workspace]` was present and the `root` field is the
     /// optional value of `package.workspace`, if present.
     Member { root: Option<String> },
+
+    /// Indicates that `package.standalone` was set, so the package never
+    /// searches for a workspace root and is never adopted as a member.
+    Standalone,
 }
 
 /// Intermediate configuration of a workspace root in a manifest.
@@ -131,6 +143,7 @@ pub struct WorkspaceRootConfig {
     default_members: Option<Vec<String>>,
     exclude: Vec<String>,
     custom_metadata: Option<toml::Value>,
+    limits: Option<TomlWorkspaceLimits>,
 }
 
 /// An iterator over the member packages of a workspace, returned by
@@ -165,6 +178,20 @@ impl<'cfg> Workspace<'cfg> {
             .and_then(|cfg| cfg.custom_metadata);
         ws.find_members()?;
         ws.set_resolve_behavior();
+        if !ws.resolver_source.is_absent() {
+            let behavior = ws.resolve_behavior;
+            let source = ws.resolver_source;
+            config.shell().verbose(|s| {
+                s.note(format!(
+                    "using resolver \"{}\" selected by {}",
+                    match behavior {
+                        ResolveBehavior::V1 => "1",
+                        ResolveBehavior::V2 => "2",
+                    },
+                    source.describe()
+                ))
+            })?;
+        }
         ws.validate()?;
         Ok(ws)
     }
@@ -187,6 +214,7 @@ impl<'cfg> Workspace<'cfg> {
             loaded_packages: RefCell::new(HashMap::new()),
             ignore_lock: false,
             resolve_behavior: ResolveBehavior::V1,
+            resolver_source: ResolverSource::Absent,
             custom_metadata: None,
         }
     }
@@ -260,6 +288,10 @@ impl<'cfg> Workspace<'cfg> {
             MaybePackage::Virtual(vm) => vm.resolve_behavior(),
         }
         .unwrap_or(ResolveBehavior::V1);
+        self.resolver_source = match self.root_maybe() {
+            MaybePackage::Package(p) => p.manifest().resolver_source(),
+            MaybePackage::Virtual(vm) => vm.resolver_source(),
+        };
     }
 
     /// Returns the current package of this workspace.
@@ -452,16 +484,6 @@ impl<'cfg> Workspace<'cfg> {
     /// Returns an error if `manifest_path` isn't actually a valid manifest or
     /// if some other transient error happens.
     fn find_root(&mut self, manifest_path: &Path) -> CargoResult<Option<PathBuf>> {
-        fn read_root_pointer(member_manifest: &Path, root_link: &str) -> PathBuf {
-            let path = member_manifest
-                .parent()
-                .unwrap()
-                .join(root_link)
-                .join("Cargo.toml");
-            debug!("find_root - pointer {}", path.display());
-            paths::normalize_path(&path)
-        }
-
         {
             let current = self.packages.load(manifest_path)?;
             match *current.workspace_config() {
@@ -471,8 +493,17 @@ impl<'cfg> Workspace<'cfg> {
                 }
                 WorkspaceConfig::Member {
                     root: Some(ref path_to_root),
-                } => return Ok(Some(read_root_pointer(manifest_path, path_to_root))),
+                } => {
+                    let path_to_root = path_to_root.clone();
+                    return self
+                        .follow_root_pointer(manifest_path, &path_to_root)
+                        .map(Some);
+                }
                 WorkspaceConfig::Member { root: None } => {}
+                WorkspaceConfig::Standalone => {
+                    debug!("find_root - is standalone {}", manifest_path.display());
+                    return Ok(None);
+                }
             }
         }
 
@@ -496,9 +527,12 @@ impl<'cfg> Workspace<'cfg> {
                         root: Some(ref path_to_root),
                     } => {
                         debug!("find_root - found pointer");
-                        return Ok(Some(read_root_pointer(&ances_manifest_path, path_to_root)));
+                        let path_to_root = path_to_root.clone();
+                        return self
+                            .follow_root_pointer(&ances_manifest_path, &path_to_root)
+                            .map(Some);
                     }
-                    WorkspaceConfig::Member { .. } => {}
+                    WorkspaceConfig::Member { .. } | WorkspaceConfig::Standalone => {}
                 }
             }
 
@@ -515,6 +549,61 @@ impl<'cfg> Workspace<'cfg> {
         Ok(None)
     }
 
+    /// Follows the `package.workspace` pointer of `member_manifest`, and the
+    /// pointers of the manifests it leads to, until a manifest without one is
+    /// reached.
+    ///
+    /// Returns an error naming every manifest on the way if the pointers form
+    /// a cycle, or if more than `MAX_WORKSPACE_POINTER_HOPS` are followed.
+    fn follow_root_pointer(
+        &mut self,
+        member_manifest: &Path,
+        root_link: &str,
+    ) -> CargoResult<PathBuf> {
+        fn read_root_pointer(member_manifest: &Path, root_link: &str) -> PathBuf {
+            let path = member_manifest
+                .parent()
+                .unwrap()
+                .join(root_link)
+                .join("Cargo.toml");
+            debug!("find_root - pointer {}", path.display());
+            paths::normalize_path(&path)
+        }
+
+        let mut chain = vec![member_manifest.to_path_buf()];
+        let mut path = read_root_pointer(member_manifest, root_link);
+        loop {
+            let is_cycle = chain.contains(&path);
+            chain.push(path.clone());
+            if is_cycle {
+                anyhow::bail!(
+                    "`package.workspace` pointers form a cycle:\n{}",
+                    display_pointer_chain(&chain)
+                );
+            }
+            // A missing manifest is reported once the root is loaded.
+            if !path.exists() {
+                return Ok(path);
+            }
+            let next = match *self.packages.load(&path)?.workspace_config() {
+                WorkspaceConfig::Member {
+                    root: Some(ref root_link),
+                } => read_root_pointer(&path, root_link),
+                _ => return Ok(path),
+            };
+            if chain.len() > MAX_WORKSPACE_POINTER_HOPS {
+                chain.push(next);
+                anyhow::bail!(
+                    "followed more than {} `package.workspace` pointers without reaching \
+                     a workspace root:\n{}",
+                    MAX_WORKSPACE_POINTER_HOPS,
+                    display_pointer_chain(&chain)
+                );
+            }
+            path = next;
+        }
+    }
+
     /// After the root of a workspace has been located, probes for all members
     /// of a workspace.
     ///
@@ -616,6 +705,24 @@ impl<'cfg> Workspace<'cfg> {
             }
         }
 
+        if let WorkspaceConfig::Standalone = *self.packages.load(&manifest_path)?.workspace_config()
+        {
+            if is_path_dep {
+                // Standalone packages are never adopted as members, so treat
+                // them the same as an entry in `workspace.exclude`.
+                return Ok(());
+            }
+            anyhow::bail!(
+                "package `{}` is listed in `workspace.members` of the manifest \
+                 located at: {}\n\
+                 but it sets `package.standalone = true`, which opts it out of \
+                 every workspace; remove it from `workspace.members` or remove \
+                 `package.standalone`",
+                manifest_path.display(),
+                root_manifest.display()
+            );
+        }
+
         debug!("find_members - {}", manifest_path.display());
         self.members.push(manifest_path.clone());
 
@@ -652,6 +759,12 @@ impl<'cfg> Workspace<'cfg> {
         self.resolve_behavior
     }
 
+    /// Returns which part of the root manifest selected
+    /// [`resolve_behavior`](Self::resolve_behavior).
+    pub fn resolver_source(&self) -> ResolverSource {
+        self.resolver_source
+    }
+
     /// Returns `true` if this workspace uses the new CLI features behavior.
     ///
     /// The old behavior only allowed choosing the features from the package
@@ -673,6 +786,11 @@ impl<'cfg> Workspace<'cfg> {
     /// 2. All workspace members agree on this one root as the root.
     /// 3. The current crate is a member of this workspace.
     fn validate(&mut self) -> CargoResult<()> {
+        // Only the root's profiles are used, so only they are checked.
+        if let Some(profiles) = self.profiles() {
+            profiles.validate_inherits(self.config)?;
+        }
+
         // The rest of the checks require a VirtualManifest or multiple members.
         if self.root_manifest.is_none() {
             return Ok(());
@@ -682,7 +800,8 @@ impl<'cfg> Workspace<'cfg> {
         self.validate_workspace_roots()?;
         self.validate_members()?;
         self.error_if_manifest_not_in_members()?;
-        self.validate_manifest()
+        self.validate_manifest()?;
+        self.validate_limits()
     }
 
     fn validate_unique_names(&self) -> CargoResult<()> {
@@ -802,7 +921,9 @@ impl<'cfg> Workspace<'cfg> {
             MaybePackage::Package(ref p) => {
                 let has_members_list = match *p.manifest().workspace_config() {
                     WorkspaceConfig::Root(ref root_config) => root_config.has_members_list(),
-                    WorkspaceConfig::Member { .. } => unreachable!(),
+                    WorkspaceConfig::Member { .. } | WorkspaceConfig::Standalone => {
+                        unreachable!()
+                    }
                 };
                 if !has_members_list {
                     format!(
@@ -869,6 +990,63 @@ impl<'cfg> Workspace<'cfg> {
         Ok(())
     }
 
+    /// Checks every member against the budgets in `[workspace.limits]`.
+    fn validate_limits(&mut self) -> CargoResult<()> {
+        let limits = match self.load_workspace_config()?.and_then(|cfg| cfg.limits) {
+            Some(limits) => limits,
+            None => return Ok(()),
+        };
+        let deny = limits.deny.unwrap_or(false);
+        for pkg in self.members() {
+            let unique_deps = |is_dev: bool| {
+                pkg.dependencies()
+                    .iter()
+                    .filter(|dep| (dep.kind() == DepKind::Development) == is_dev)
+                    .map(|dep| dep.name_in_toml())
+                    .collect::<HashSet<_>>()
+                    .len()
+            };
+            let features = pkg.manifest().original().features().map_or(0, |f| f.len());
+            let counts = [
+                (
+                    "direct dependencies",
+                    "max-direct-dependencies",
+                    unique_deps(false),
+                    limits.max_direct_dependencies,
+                ),
+                (
+                    "dev-dependencies",
+                    "max-dev-dependencies",
+                    unique_deps(true),
+                    limits.max_dev_dependencies,
+                ),
+                ("features", "max-features", features, limits.max_features),
+            ];
+            for &(what, key, count, limit) in &counts {
+                let limit = match limit {
+                    Some(limit) if count > limit => limit,
+                    _ => continue,
+                };
+                let msg = format!(
+                    "package `{}` has {} {}, more than the limit of {} set by \
+                     `workspace.limits.{}`\n\
+                     package: {}",
+                    pkg.name(),
+                    count,
+                    what,
+                    limit,
+                    key,
+                    pkg.manifest_path().display(),
+                );
+                if deny {
+                    anyhow::bail!("{}", msg);
+                }
+                self.config.shell().warn(msg)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn load(&self, manifest_path: &Path) -> CargoResult<Package> {
         match self.packages.maybe_get(manifest_path) {
             Some(&MaybePackage::Package(ref p)) => return Ok(p.clone()),
@@ -921,6 +1099,9 @@ impl<'cfg> Workspace<'cfg> {
             };
             let path = path.join("Cargo.toml");
             for warning in warnings {
+                if warning.emitted {
+                    continue;
+                }
                 if warning.is_critical {
                     let err = anyhow::format_err!("{}", warning.message);
                     let cx =
@@ -1223,6 +1404,7 @@ impl WorkspaceRootConfig {
         default_members: &Option<Vec<String>>,
         exclude: &Option<Vec<String>>,
         custom_metadata: &Option<toml::Value>,
+        limits: &Option<TomlWorkspaceLimits>,
     ) -> WorkspaceRootConfig {
         WorkspaceRootConfig {
             root_dir: root_dir.to_path_buf(),
@@ -1230,6 +1412,7 @@ impl WorkspaceRootConfig {
             default_members: default_members.clone(),
             exclude: exclude.clone().unwrap_or_default(),
             custom_metadata: custom_metadata.clone(),
+            limits: limits.clone(),
         }
     }
 
@@ -1240,7 +1423,7 @@ impl WorkspaceRootConfig {
         let excluded = self
             .exclude
             .iter()
-            .any(|ex| manifest_path.starts_with(self.root_dir.join(ex)));
+            .any(|ex| self.exclude_matches(ex, manifest_path));
 
         let explicit_member = match self.members {
             Some(ref members) => members
@@ -1252,6 +1435,41 @@ impl WorkspaceRootConfig {
         !explicit_member && excluded
     }
 
+    /// Checks a single `exclude` entry against the path.
+    ///
+    /// Plain entries exclude everything below the directory they name. Entries
+    /// containing glob syntax (`*`, `**`, `?` or `[...]`) exclude a path if the
+    /// pattern matches it or any of its ancestors below the workspace root.
+    /// Components are always joined with `/`, so patterns behave the same on
+    /// every platform.
+    fn exclude_matches(&self, exclude: &str, manifest_path: &Path) -> bool {
+        let pattern = if exclude.contains(&['*', '?', '['][..]) {
+            Pattern::new(exclude).ok()
+        } else {
+            None
+        };
+        let pattern = match pattern {
+            Some(pattern) => pattern,
+            None => return manifest_path.starts_with(self.root_dir.join(exclude)),
+        };
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        manifest_path
+            .ancestors()
+            .filter_map(|dir| dir.strip_prefix(&self.root_dir).ok())
+            .filter(|rel| rel.components().next().is_some())
+            .any(|rel| {
+                let rel = rel
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                pattern.matches_with(&rel, options)
+            })
+    }
+
     fn has_members_list(&self) -> bool {
         self.members.is_some()
     }
@@ -1299,3 +1517,17 @@ impl WorkspaceRootConfig {
         Ok(res)
     }
 }
+
+/// Formats a chain of manifest paths as one indented line per manifest,
+/// with an arrow in front of each manifest that a pointer leads to.
+fn display_pointer_chain(chain: &[PathBuf]) -> String {
+    chain
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let arrow = if i == 0 { "" } else { "-> " };
+            format!("  {}{}", arrow, path.display())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
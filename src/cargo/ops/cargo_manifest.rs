@@ -0,0 +1,140 @@
+//! Implementation of the `cargo manifest` family of commands, which edit or
+//! inspect a `Cargo.toml`.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::bail;
+
+use crate::core::Workspace;
+use crate::ops;
+use crate::util::errors::{CargoResult, CargoResultExt};
+use crate::util::{paths, Config};
+
+/// The tables that hold dependencies, both at the top level of a manifest
+/// and inside each `[target.'...']` table.
+const DEP_TABLES: &[&str] = &[
+    "dependencies",
+    "dev-dependencies",
+    "dev_dependencies",
+    "build-dependencies",
+    "build_dependencies",
+];
+
+/// Sorts the entries of every dependency table in the manifest at
+/// `manifest_path` alphabetically by name.
+///
+/// The manifest is edited in place with `toml_edit`, so comments and the
+/// formatting of each entry are preserved. The file is only rewritten if the
+/// order actually changed.
+pub fn sort_deps(manifest_path: &Path, config: &Config) -> CargoResult<()> {
+    let contents = paths::read(manifest_path)?;
+    let mut doc = contents
+        .parse::<toml_edit::Document>()
+        .chain_err(|| format!("failed to parse manifest at `{}`", manifest_path.display()))?;
+
+    let root = doc.as_table_mut();
+    sort_dep_tables(root);
+    if let Some(targets) = root.get_mut("target").and_then(|t| t.as_table_mut()) {
+        let platforms: Vec<String> = targets.iter().map(|(k, _)| k.to_string()).collect();
+        for platform in platforms {
+            if let Some(table) = targets.get_mut(&platform).and_then(|t| t.as_table_mut()) {
+                sort_dep_tables(table);
+            }
+        }
+    }
+
+    let sorted = doc.to_string_in_original_order();
+    if sorted == contents {
+        config
+            .shell()
+            .status("Unchanged", manifest_path.display())?;
+    } else {
+        paths::write(manifest_path, sorted.as_bytes())?;
+        config.shell().status("Sorted", manifest_path.display())?;
+    }
+    Ok(())
+}
+
+fn sort_dep_tables(table: &mut toml_edit::Table) {
+    for name in DEP_TABLES {
+        if let Some(deps) = table.get_mut(name).and_then(|t| t.as_table_mut()) {
+            deps.sort_values();
+        }
+    }
+}
+
+/// Checks that no dependency of a workspace member declares a `rust-version`
+/// newer than the member's own `rust-version`.
+///
+/// The dependencies are taken from the lock file, which is generated if it
+/// doesn't exist yet. Members without a `rust-version`, and dependencies that
+/// don't declare one, are skipped.
+pub fn check_min_rust_version(ws: &Workspace<'_>) -> CargoResult<()> {
+    let (pkg_set, resolve) = ops::resolve_ws(ws)?;
+    let mut errors = Vec::new();
+    for member in ws.members() {
+        let rust_version = match member.rust_version() {
+            Some(rust_version) => rust_version,
+            None => continue,
+        };
+
+        let mut dep_ids = HashSet::new();
+        let mut queue = vec![member.package_id()];
+        while let Some(id) = queue.pop() {
+            for (dep_id, _) in resolve.deps(id) {
+                if dep_ids.insert(dep_id) {
+                    queue.push(dep_id);
+                }
+            }
+        }
+
+        let mut newer = Vec::new();
+        for dep in pkg_set.get_many(dep_ids)? {
+            if let Some(dep_rust_version) = dep.rust_version() {
+                if rust_version_key(dep_rust_version) > rust_version_key(rust_version) {
+                    newer.push((dep.package_id(), dep_rust_version));
+                }
+            }
+        }
+        if newer.is_empty() {
+            ws.config().shell().status(
+                "Checked",
+                format!(
+                    "dependencies of {} against rust-version {}",
+                    member.package_id(),
+                    rust_version
+                ),
+            )?;
+            continue;
+        }
+        newer.sort();
+        let newer: Vec<_> = newer
+            .iter()
+            .map(|(id, dep_rust_version)| {
+                format!("  {} requires rust-version {}", id, dep_rust_version)
+            })
+            .collect();
+        errors.push(format!(
+            "package `{}` has rust-version {}, but some of its dependencies \
+             require a newer Rust:\n{}",
+            member.name(),
+            rust_version,
+            newer.join("\n")
+        ));
+    }
+    if !errors.is_empty() {
+        bail!("{}", errors.join("\n\n"));
+    }
+    Ok(())
+}
+
+/// Splits a `rust-version` like `1.56` into its major, minor and patch
+/// numbers, with the missing ones as zero, so versions compare numerically.
+fn rust_version_key(rust_version: &str) -> [u64; 3] {
+    let mut key = [0; 3];
+    for (slot, part) in key.iter_mut().zip(rust_version.split('.')) {
+        *slot = part.parse().unwrap_or(0);
+    }
+    key
+}
@@ -56,6 +56,9 @@ enum FileContents {
 enum GeneratedFile {
     /// Generates `Cargo.toml` by rewriting the original.
     Manifest,
+    /// Generates `Cargo.toml.orig` from the original at the given path, with
+    /// the tables named by `package.publish-strip-metadata` removed.
+    OrigManifest(PathBuf),
     /// Generates `Cargo.lock` in some cases (like if there is a binary).
     Lockfile,
     /// Adds a `.cargo-vcs_info.json` file if in a (clean) git repo.
@@ -108,6 +111,14 @@ pub fn package(ws: &Workspace<'_>, opts: &PackageOpts<'_>) -> CargoResult<Option
 
     verify_dependencies(pkg)?;
 
+    let stripped = pkg.manifest().original().publish_stripped_metadata();
+    if !stripped.is_empty() {
+        config.shell().note(format!(
+            "leaving `{}` out of the published manifest",
+            stripped.join("`, `")
+        ))?;
+    }
+
     let filename = format!("{}-{}.crate", pkg.name(), pkg.version());
     let dir = ws.target_dir().join("package");
     let mut dst = {
@@ -159,10 +170,20 @@ fn build_ar_list(
             .to_string();
         match rel_str.as_ref() {
             "Cargo.toml" => {
+                let orig = if pkg
+                    .manifest()
+                    .original()
+                    .publish_strip_metadata()
+                    .is_empty()
+                {
+                    FileContents::OnDisk(src_file)
+                } else {
+                    FileContents::Generated(GeneratedFile::OrigManifest(src_file))
+                };
                 result.push(ArchiveFile {
                     rel_path: PathBuf::from("Cargo.toml.orig"),
                     rel_str: "Cargo.toml.orig".to_string(),
-                    contents: FileContents::OnDisk(src_file),
+                    contents: orig,
                 });
                 result.push(ArchiveFile {
                     rel_path,
@@ -519,6 +540,11 @@ fn tar(
             FileContents::Generated(generated_kind) => {
                 let contents = match generated_kind {
                     GeneratedFile::Manifest => pkg.to_registry_toml(ws)?,
+                    GeneratedFile::OrigManifest(path) => strip_orig_manifest(
+                        &paths::read(&path)?,
+                        pkg.manifest().original().publish_strip_metadata(),
+                    )
+                    .chain_err(|| format!("failed to parse manifest at `{}`", path.display()))?,
                     GeneratedFile::Lockfile => build_lock(ws)?,
                     GeneratedFile::VcsInfo(s) => s,
                 };
@@ -537,6 +563,38 @@ fn tar(
     Ok(())
 }
 
+/// Removes `package.publish-strip-metadata` and the `[package.metadata]` and
+/// `[badges]` keys it lists from the original manifest text. Everything else
+/// is kept as written.
+fn strip_orig_manifest(contents: &str, keys: &[String]) -> CargoResult<String> {
+    fn remove_keys(item: Option<&mut toml_edit::Item>, keys: &[String]) {
+        let item = match item {
+            Some(item) => item,
+            None => return,
+        };
+        if let Some(table) = item.as_table_mut() {
+            for key in keys {
+                table.remove(key);
+            }
+        } else if let Some(table) = item.as_value_mut().and_then(|v| v.as_inline_table_mut()) {
+            for key in keys {
+                table.remove(key);
+            }
+        }
+    }
+
+    let mut doc = contents.parse::<toml_edit::Document>()?;
+    let root = doc.as_table_mut();
+    for name in &["package", "project"] {
+        if let Some(package) = root.get_mut(name).and_then(|t| t.as_table_mut()) {
+            package.remove("publish-strip-metadata");
+            remove_keys(package.get_mut("metadata"), keys);
+        }
+    }
+    remove_keys(root.get_mut("badges"), keys);
+    Ok(doc.to_string_in_original_order())
+}
+
 /// Generate warnings when packaging Cargo.lock, and the resolve have changed.
 fn compare_resolve(
     config: &Config,
@@ -252,7 +252,8 @@ fn transmit(
         ref license_file,
         ref categories,
         ref badges,
-        ref links,
+        links: _,
+        funding: _,
     } = *manifest.metadata();
     let readme_content = readme
         .as_ref()
@@ -267,6 +268,14 @@ fn transmit(
         }
     }
 
+    // Badges listed in `package.publish-strip-metadata` are not published.
+    let strip = manifest.original().publish_strip_metadata();
+    let badges = badges
+        .iter()
+        .filter(|(name, _)| !strip.contains(name))
+        .map(|(name, badge)| (name.clone(), badge.clone()))
+        .collect();
+
     // Do not upload if performing a dry run
     if dry_run {
         config.shell().warn("aborting upload due to dry run")?;
@@ -304,8 +313,10 @@ fn transmit(
                 repository: repository.clone(),
                 license: license.clone(),
                 license_file: license_file.clone(),
-                badges: badges.clone(),
-                links: links.clone(),
+                badges,
+                // The summary's key includes the `links-version`, so the
+                // index keeps incompatible versions of the library apart.
+                links: pkg.summary().links().map(|links| links.to_string()),
                 v: None,
             },
             tarball,
@@ -17,7 +17,7 @@ use crate::core::resolver::{self, HasDevUnits, Resolve, ResolveOpts, ResolveVers
 use crate::core::summary::Summary;
 use crate::core::Feature;
 use crate::core::{
-    GitReference, PackageId, PackageIdSpec, PackageSet, Source, SourceId, Workspace,
+    Dependency, GitReference, PackageId, PackageIdSpec, PackageSet, Source, SourceId, Workspace,
 };
 use crate::ops;
 use crate::sources::PathSource;
@@ -240,6 +240,7 @@ pub fn resolve_with_previous<'cfg>(
     // locked.
     let mut avoid_patch_ids = HashSet::new();
     if register_patches {
+        check_patch_versions(ws)?;
         for (url, patches) in ws.root_patch() {
             let previous = match previous {
                 Some(r) => r,
@@ -427,6 +428,90 @@ pub fn get_resolved_packages<'cfg>(
     registry.get(&ids)
 }
 
+/// Checks that each `[patch]` entry of the workspace root whose version is
+/// known up front satisfies the requirement of at least one direct dependency
+/// of a member that it patches.
+///
+/// Mismatches are warnings unless `-Z strict-patch-versions` is set.
+fn check_patch_versions(ws: &Workspace<'_>) -> CargoResult<()> {
+    let config = ws.config();
+    let strict = config.cli_unstable().strict_patch_versions;
+    for (url, patches) in ws.root_patch() {
+        for patch_dep in patches {
+            let version = match patched_version(ws, patch_dep, strict)? {
+                Some(version) => version,
+                None => continue,
+            };
+            let patched: Vec<_> = ws
+                .members()
+                .flat_map(|pkg| pkg.dependencies())
+                .filter(|dep| {
+                    dep.package_name() == patch_dep.package_name()
+                        && dep.source_id().is_registry()
+                        && dep.source_id().url() == url
+                })
+                .collect();
+            // A package may be patched with several versions at once, each
+            // meant for a different dependency on it.
+            if patched
+                .iter()
+                .any(|dep| dep.version_req().matches(&version))
+            {
+                continue;
+            }
+            let mut reqs = HashSet::new();
+            for dep in patched {
+                if !reqs.insert(dep.version_req().to_string()) {
+                    continue;
+                }
+                let msg = format!(
+                    "patch for `{}` version {} does not satisfy the dependency \
+                     requirement `{}`",
+                    patch_dep.package_name(),
+                    version,
+                    dep.version_req()
+                );
+                if strict {
+                    anyhow::bail!("{}", msg);
+                }
+                config.shell().warn(msg)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the version a `[patch]` entry provides, if it can be determined
+/// without resolving: either an exact `=x.y.z` requirement, or the version
+/// of the package at a `path` patch.
+///
+/// A `path` patch that fails to load is skipped, unless `strict` is set.
+fn patched_version(
+    ws: &Workspace<'_>,
+    dep: &Dependency,
+    strict: bool,
+) -> CargoResult<Option<semver::Version>> {
+    let req = dep.version_req().to_string();
+    if let Some(exact) = req.strip_prefix('=') {
+        return Ok(semver::Version::parse(exact.trim()).ok());
+    }
+    if !dep.source_id().is_path() {
+        return Ok(None);
+    }
+    let path = match dep.source_id().url().to_file_path() {
+        Ok(path) => path,
+        Err(()) => return Ok(None),
+    };
+    match ws.load(&path.join("Cargo.toml")) {
+        Ok(pkg) => Ok(Some(pkg.version().clone())),
+        Err(e) if strict => Err(e.context(format!(
+            "failed to load the patch for `{}` to check its version",
+            dep.package_name()
+        ))),
+        Err(_) => Ok(None),
+    }
+}
+
 /// In this function we're responsible for informing the `registry` of all
 /// locked dependencies from the previous lock file we had, `resolve`.
 ///
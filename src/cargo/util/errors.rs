@@ -130,6 +130,7 @@ impl fmt::Display for InternalError {
 pub struct ManifestError {
     cause: Error,
     manifest: PathBuf,
+    position: Option<(usize, usize)>,
 }
 
 impl ManifestError {
@@ -137,13 +138,26 @@ impl ManifestError {
         Self {
             cause: cause.into(),
             manifest,
+            position: None,
         }
     }
 
+    /// Records the 1-based line and column in the manifest that this error
+    /// is about.
+    pub fn with_position(mut self, line: usize, column: usize) -> Self {
+        self.position = Some((line, column));
+        self
+    }
+
     pub fn manifest_path(&self) -> &PathBuf {
         &self.manifest
     }
 
+    /// The 1-based line and column of the key this error is about, if known.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        self.position
+    }
+
     /// Returns an iterator over the `ManifestError` chain of causes.
     ///
     /// So if this error was not caused by another `ManifestError` this will be empty.
@@ -170,6 +184,47 @@ impl fmt::Display for ManifestError {
     }
 }
 
+/// An error caused by the value of a particular key in a manifest.
+///
+/// The key is the full path of table names, like `["dependencies", "bar"]`.
+/// When this is found in the chain of causes, `read_manifest` reports where
+/// the key appears in the file.
+pub struct ManifestKeyError {
+    cause: Error,
+    key: Vec<String>,
+}
+
+impl ManifestKeyError {
+    pub fn new<E: Into<Error>>(cause: E, key: Vec<String>) -> Self {
+        Self {
+            cause: cause.into(),
+            key,
+        }
+    }
+
+    pub fn key(&self) -> &[String] {
+        &self.key
+    }
+}
+
+impl std::error::Error for ManifestKeyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause.source()
+    }
+}
+
+impl fmt::Debug for ManifestKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cause.fmt(f)
+    }
+}
+
+impl fmt::Display for ManifestKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cause.fmt(f)
+    }
+}
+
 /// An iterator over the `ManifestError` chain of causes.
 pub struct ManifestCauses<'a> {
     current: &'a ManifestError,
@@ -1,5 +1,7 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
+use std::fs::File;
+use std::hash::Hasher;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::str;
@@ -13,14 +15,16 @@ use serde::ser;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::core::dependency::DepKind;
-use crate::core::manifest::{ManifestMetadata, TargetSourcePath, Warnings};
-use crate::core::resolver::ResolveBehavior;
+use crate::core::dependency::{Artifact, DepKind};
+use crate::core::manifest::{
+    DeprecatedSyntax, ManifestMetadata, TargetSourcePath, WarningCategory, Warnings,
+};
+use crate::core::resolver::{ResolveBehavior, ResolverSource};
 use crate::core::{Dependency, Manifest, PackageId, Summary, Target};
 use crate::core::{Edition, EitherManifest, Feature, Features, VirtualManifest, Workspace};
 use crate::core::{GitReference, PackageIdSpec, SourceId, WorkspaceConfig, WorkspaceRootConfig};
 use crate::sources::{CRATES_IO_INDEX, CRATES_IO_REGISTRY};
-use crate::util::errors::{CargoResult, CargoResultExt, ManifestError};
+use crate::util::errors::{CargoResult, CargoResultExt, ManifestError, ManifestKeyError};
 use crate::util::interning::InternedString;
 use crate::util::{self, paths, validate_package_name, Config, IntoUrl};
 
@@ -47,9 +51,160 @@ pub fn read_manifest(
     );
     let contents = paths::read(path).map_err(|err| ManifestError::new(err, path.into()))?;
 
-    do_read_manifest(&contents, path, source_id, config)
-        .chain_err(|| format!("failed to parse manifest at `{}`", path.display()))
-        .map_err(|err| ManifestError::new(err, path.into()))
+    do_read_manifest(&contents, path, source_id, config).map_err(|err| {
+        let position = err
+            .chain()
+            .find_map(|e| e.downcast_ref::<ManifestKeyError>())
+            .and_then(|e| key_position(&contents, e.key()));
+        let location = match position {
+            Some((line, column)) => format!("{}:{}:{}", path.display(), line, column),
+            None => path.display().to_string(),
+        };
+        let err = ManifestError::new(
+            err.context(format!("failed to parse manifest at `{}`", location)),
+            path.into(),
+        );
+        match position {
+            Some((line, column)) => err.with_position(line, column),
+            None => err,
+        }
+    })
+}
+
+/// Finds the 1-based line and column where `key` is defined in the TOML
+/// text `contents`.
+///
+/// The line is the one holding the last segment of `key`, either a table
+/// header like `[dependencies.bar]` or a `bar = ...` entry, and the column
+/// is where that line's content starts. Keys are compared exactly, except
+/// that the `dev-dependencies` and `build-dependencies` tables also match
+/// their underscore aliases. This is best-effort: `None` is returned if the
+/// key isn't found or the spans can't be read.
+fn key_position(contents: &str, key: &[String]) -> Option<(usize, usize)> {
+    let mut parser = toml::de::Deserializer::new(contents);
+    parser.set_require_newline_after_table(false);
+    parser.set_allow_duplicate_after_longer_table(true);
+    let mut item = KeySpans::deserialize(&mut parser).ok()?;
+    let mut start = None;
+    for segment in key {
+        let segment = segment.as_str();
+        let aliases = match segment {
+            "dev-dependencies" => &["dev-dependencies", "dev_dependencies"][..],
+            "build-dependencies" => &["build-dependencies", "build_dependencies"][..],
+            _ => std::slice::from_ref(&segment),
+        };
+        let entries = match item {
+            KeySpans::Table(entries) => entries,
+            KeySpans::Other => return None,
+        };
+        let (name, value) = entries
+            .into_iter()
+            .find(|(name, _)| aliases.contains(&name.get_ref().as_str()))?;
+        start = Some(name.start());
+        item = value;
+    }
+
+    let line_start = contents[..start?].rfind('\n').map_or(0, |i| i + 1);
+    let line = contents[..line_start].matches('\n').count() + 1;
+    let text = &contents[line_start..];
+    let column = text.len() - text.trim_start().len() + 1;
+    Some((line, column))
+}
+
+/// The keys of a TOML document along with where each of them is defined,
+/// used by `key_position`.
+enum KeySpans {
+    Table(Vec<(toml::Spanned<String>, KeySpans)>),
+    Other,
+}
+
+impl<'de> de::Deserialize<'de> for KeySpans {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct KeySpansVisitor;
+
+        impl<'de> de::Visitor<'de> for KeySpansVisitor {
+            type Value = KeySpans;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a TOML value")
+            }
+
+            fn visit_bool<E>(self, _v: bool) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(KeySpans::Other)
+            }
+
+            fn visit_i64<E>(self, _v: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(KeySpans::Other)
+            }
+
+            fn visit_f64<E>(self, _v: f64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(KeySpans::Other)
+            }
+
+            fn visit_str<E>(self, _v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(KeySpans::Other)
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                while seq.next_element::<de::IgnoredAny>()?.is_some() {}
+                Ok(KeySpans::Other)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let mut entries = Vec::new();
+                while let Some(key) = map.next_key()? {
+                    entries.push((key, map.next_value()?));
+                }
+                Ok(KeySpans::Table(entries))
+            }
+        }
+
+        deserializer.deserialize_any(KeySpansVisitor)
+    }
+}
+
+/// Hashes the raw contents of a `Cargo.toml`.
+///
+/// The hash is a `StableHasher` digest of the bytes alone, so the same
+/// contents always produce the same value with a given version of Cargo,
+/// independent of the path, the process, or the platform. It is not
+/// guaranteed to be stable across Cargo versions.
+pub fn manifest_contents_hash(contents: &str) -> u64 {
+    let mut hasher = util::StableHasher::new();
+    hasher.write(contents.as_bytes());
+    hasher.finish()
+}
+
+/// Checks whether the `Cargo.toml` at `path` still has the contents that
+/// produced `hash`, as returned by `Manifest::contents_hash`, without parsing
+/// it again.
+pub fn manifest_is_current(path: &Path, hash: u64) -> CargoResult<bool> {
+    let file = File::open(path)
+        .chain_err(|| format!("failed to open manifest at `{}`", path.display()))?;
+    let current = util::hex::hash_u64_file(&file)
+        .chain_err(|| format!("failed to read manifest at `{}`", path.display()))?;
+    Ok(current == hash)
 }
 
 fn do_read_manifest(
@@ -60,12 +215,22 @@ fn do_read_manifest(
 ) -> CargoResult<(EitherManifest, Vec<PathBuf>)> {
     let package_root = manifest_file.parent().unwrap();
 
-    let toml = {
+    let ParseOutput {
+        value: toml,
+        deprecated_syntax,
+        warnings: parse_warnings,
+    } = {
         let pretty_filename = manifest_file
             .strip_prefix(config.cwd())
             .unwrap_or(manifest_file);
-        parse(contents, pretty_filename, config)?
+        parse_document(contents, pretty_filename, config.cli_unstable().strict_toml)?
     };
+    // These are printed right away rather than through the manifest's
+    // warnings, which are only shown for workspace members and are lost if
+    // the conversion below fails.
+    for warning in &parse_warnings {
+        config.shell().warn(warning)?;
+    }
 
     // Provide a helpful error message for a common user error.
     if let Some(package) = toml.get("package").or_else(|| toml.get("project")) {
@@ -80,15 +245,35 @@ fn do_read_manifest(
 
     let mut unused = BTreeSet::new();
     let manifest: TomlManifest = serde_ignored::deserialize(toml, |path| {
-        let mut key = String::new();
-        stringify(&mut key, &path);
-        unused.insert(key);
+        let mut segments = Vec::new();
+        stringify(&mut segments, &path);
+        let suggestion = suggest_key(&segments);
+        unused.insert((segments.join("."), suggestion));
     })?;
-    let add_unused = |warnings: &mut Warnings| {
-        for key in unused {
-            warnings.add_warning(format!("unused manifest key: {}", key));
+
+    // Checked here rather than while converting the manifest, so that it
+    // applies the same way to real and virtual manifests.
+    if manifest.patch.is_some() && manifest.replace.is_some() {
+        bail!("cannot specify both [replace] and [patch]");
+    }
+    let add_warnings = |warnings: &mut Warnings| {
+        for warning in parse_warnings {
+            warnings.push_emitted(WarningCategory::Deprecated, warning);
+        }
+        for (key, suggestion) in unused {
+            let msg = match suggestion {
+                Some(suggestion) => format!(
+                    "unused manifest key: {} (did you mean `{}`?)",
+                    key, suggestion
+                ),
+                None => format!("unused manifest key: {}", key),
+            };
+            warnings.push(WarningCategory::UnusedKey, msg);
             if key == "profiles.debug" {
-                warnings.add_warning("use `[profile.dev]` to configure debug builds".to_string());
+                warnings.push(
+                    WarningCategory::UnusedKey,
+                    "use `[profile.dev]` to configure debug builds".to_string(),
+                );
             }
         }
     };
@@ -97,7 +282,9 @@ fn do_read_manifest(
     return if manifest.project.is_some() || manifest.package.is_some() {
         let (mut manifest, paths) =
             TomlManifest::to_real_manifest(&manifest, source_id, package_root, config)?;
-        add_unused(manifest.warnings_mut());
+        add_warnings(manifest.warnings_mut());
+        manifest.deprecated_syntax_mut().insert(deprecated_syntax);
+        manifest.set_contents_hash(manifest_contents_hash(contents));
         if manifest.targets().iter().all(|t| t.is_custom_build()) {
             bail!(
                 "no targets specified in the manifest\n\
@@ -109,28 +296,23 @@ fn do_read_manifest(
     } else {
         let (mut m, paths) =
             TomlManifest::to_virtual_manifest(&manifest, source_id, package_root, config)?;
-        add_unused(m.warnings_mut());
+        add_warnings(m.warnings_mut());
+        m.set_contents_hash(manifest_contents_hash(contents));
         Ok((EitherManifest::Virtual(m), paths))
     };
 
-    fn stringify(dst: &mut String, path: &serde_ignored::Path<'_>) {
+    fn stringify(dst: &mut Vec<String>, path: &serde_ignored::Path<'_>) {
         use serde_ignored::Path;
 
         match *path {
             Path::Root => {}
             Path::Seq { parent, index } => {
                 stringify(dst, parent);
-                if !dst.is_empty() {
-                    dst.push('.');
-                }
-                dst.push_str(&index.to_string());
+                dst.push(index.to_string());
             }
             Path::Map { parent, ref key } => {
                 stringify(dst, parent);
-                if !dst.is_empty() {
-                    dst.push('.');
-                }
-                dst.push_str(key);
+                dst.push(key.clone());
             }
             Path::Some { parent }
             | Path::NewtypeVariant { parent }
@@ -139,17 +321,180 @@ fn do_read_manifest(
     }
 }
 
+/// Keys that are valid at the top level of a manifest.
+const MANIFEST_KEYS: &[&str] = &[
+    "cargo-features",
+    "package",
+    "profile",
+    "lib",
+    "bin",
+    "example",
+    "test",
+    "bench",
+    "dependencies",
+    "dev-dependencies",
+    "build-dependencies",
+    "features",
+    "target",
+    "replace",
+    "patch",
+    "workspace",
+    "badges",
+    "lints",
+];
+
+/// Keys that are valid in `[package]`.
+const PACKAGE_KEYS: &[&str] = &[
+    "edition",
+    "rust-version",
+    "name",
+    "version",
+    "authors",
+    "build",
+    "metabuild",
+    "links",
+    "links-version",
+    "exclude",
+    "include",
+    "publish",
+    "workspace",
+    "standalone",
+    "autobins",
+    "autoexamples",
+    "autotests",
+    "autobenches",
+    "default-run",
+    "publish-strip-metadata",
+    "description",
+    "homepage",
+    "documentation",
+    "readme",
+    "keywords",
+    "categories",
+    "license",
+    "license-file",
+    "repository",
+    "funding",
+    "env",
+    "metadata",
+    "resolver",
+];
+
+/// Keys that are valid in a `[profile]` table.
+const PROFILE_KEYS: &[&str] = &[
+    "opt-level",
+    "lto",
+    "codegen-units",
+    "debug",
+    "split-debuginfo",
+    "debug-assertions",
+    "rpath",
+    "panic",
+    "overflow-checks",
+    "incremental",
+    "package",
+    "build-override",
+    "dir-name",
+    "inherits",
+    "merge-policy",
+    "strip",
+    "trim-paths",
+    "package-defaults",
+    "self-profile",
+];
+
+/// Keys that are valid in a detailed dependency.
+const DEPENDENCY_KEYS: &[&str] = &[
+    "version",
+    "registry",
+    "path",
+    "git",
+    "registry-version",
+    "branch",
+    "tag",
+    "rev",
+    "features",
+    "optional",
+    "default-features",
+    "package",
+    "public",
+    "artifact",
+    "lib",
+    "target",
+    "minimum-abi-version",
+    "checksum",
+];
+
+/// Suggests a known key to use instead of the unused key at `path`, if one
+/// is close enough to be a likely misspelling.
+fn suggest_key(path: &[String]) -> Option<&'static str> {
+    const DEPENDENCY_TABLES: &[&str] = &[
+        "dependencies",
+        "dev-dependencies",
+        "dev_dependencies",
+        "build-dependencies",
+        "build_dependencies",
+    ];
+
+    let (key, table) = path.split_last()?;
+    let table: Vec<&str> = table.iter().map(|s| s.as_str()).collect();
+    let known = match table.as_slice() {
+        [] => MANIFEST_KEYS,
+        ["package"] | ["project"] => PACKAGE_KEYS,
+        ["profile", _] | ["profile", _, "build-override"] | ["profile", _, "package", _] => {
+            PROFILE_KEYS
+        }
+        ["patch", _, _] | ["replace", _] => DEPENDENCY_KEYS,
+        [deps, _] | ["target", _, deps, _] if DEPENDENCY_TABLES.contains(deps) => DEPENDENCY_KEYS,
+        _ => return None,
+    };
+    util::closest(key, known.iter().copied(), |k| *k)
+}
+
 /// Attempts to parse a string into a [`toml::Value`]. This is not specific to any
 /// particular kind of TOML file.
 ///
 /// The purpose of this wrapper is to detect invalid TOML which was previously
 /// accepted and display a warning to the user in that case. The `file` and `config`
-/// parameters are only used by this fallback path.
+/// parameters are only used by this fallback path. With `-Z strict-toml`, such
+/// TOML is an error instead, in every file parsed, including the manifests of
+/// registry and git dependencies.
 pub fn parse(toml: &str, file: &Path, config: &Config) -> CargoResult<toml::Value> {
+    let output = parse_document(toml, file, config.cli_unstable().strict_toml)?;
+    for warning in &output.warnings {
+        config.shell().warn(warning)?;
+    }
+    Ok(output.value)
+}
+
+/// The result of [`parse_document`].
+struct ParseOutput {
+    value: toml::Value,
+    /// [`DeprecatedSyntax::LEGACY_TOML`] if one of the fallback parsers was
+    /// needed.
+    deprecated_syntax: DeprecatedSyntax,
+    /// Warnings about syntax that only the fallback parsers accept.
+    warnings: Vec<String>,
+}
+
+/// Like [`parse`], but returns the warnings instead of printing them.
+///
+/// If `strict` is set, the fallback parsers for historically accepted syntax
+/// are skipped and the original parse error is returned.
+fn parse_document(toml: &str, file: &Path, strict: bool) -> CargoResult<ParseOutput> {
     let first_error = match toml.parse() {
-        Ok(ret) => return Ok(ret),
+        Ok(value) => {
+            return Ok(ParseOutput {
+                value,
+                deprecated_syntax: DeprecatedSyntax::default(),
+                warnings: Vec::new(),
+            })
+        }
         Err(e) => e,
     };
+    if strict {
+        return Err(toml_parse_error(first_error, file));
+    }
 
     let mut second_parser = toml::de::Deserializer::new(toml);
     second_parser.set_require_newline_after_table(false);
@@ -165,14 +510,17 @@ it. A newline needs to be added and this warning will soon become a hard error
 in the future.",
             file.display()
         );
-        config.shell().warn(&msg)?;
-        return Ok(ret);
+        return Ok(ParseOutput {
+            value: ret,
+            deprecated_syntax: DeprecatedSyntax::LEGACY_TOML,
+            warnings: vec![msg],
+        });
     }
 
     let mut third_parser = toml::de::Deserializer::new(toml);
     third_parser.set_allow_duplicate_after_longer_table(true);
     if let Ok(ret) = toml::Value::deserialize(&mut third_parser) {
-        let msg = format!(
+        let mut msg = format!(
             "\
 TOML file found which contains invalid syntax and will soon not parse
 at `{}`.
@@ -183,12 +531,71 @@ definitions will need to be merged together with one table header to proceed,
 and this will become a hard error in the future.",
             file.display()
         );
-        config.shell().warn(&msg)?;
-        return Ok(ret);
+        if let Some((header, first, second)) = find_duplicate_table(toml) {
+            msg.push_str(&format!(
+                "\n\n\
+duplicate table header `[{0}]` found at line {2} (first defined at line {1}),
+move the keys below line {2} into the first `[{0}]` table and remove the
+second header.",
+                header, first, second
+            ));
+        }
+        return Ok(ParseOutput {
+            value: ret,
+            deprecated_syntax: DeprecatedSyntax::LEGACY_TOML,
+            warnings: vec![msg],
+        });
     }
 
-    let first_error = anyhow::Error::from(first_error);
-    Err(first_error.context("could not parse input as TOML"))
+    Err(toml_parse_error(first_error, file))
+}
+
+/// Wraps a TOML syntax error with the file it came from, and the 1-based
+/// line and column of the error when `toml` reports one.
+fn toml_parse_error(error: toml::de::Error, file: &Path) -> anyhow::Error {
+    let location = match error.line_col() {
+        Some((line, col)) => format!("{}:{}:{}", file.display(), line + 1, col + 1),
+        None => file.display().to_string(),
+    };
+    anyhow::Error::from(error).context(format!("could not parse input as TOML at `{}`", location))
+}
+
+/// Scans the raw TOML text for a `[table]` header that appears more than once.
+///
+/// Returns the header name along with the 1-based line numbers of its first
+/// and second definitions. Array-of-tables headers (`[[bin]]`) are ignored,
+/// since repeating those is valid.
+fn find_duplicate_table(toml: &str) -> Option<(String, usize, usize)> {
+    let mut seen = HashMap::new();
+    for (i, line) in toml.lines().enumerate() {
+        let line = line.trim_start();
+        if !line.starts_with('[') || line.starts_with("[[") {
+            continue;
+        }
+        let end = match line.find(']') {
+            Some(end) => end,
+            None => continue,
+        };
+        let header = line[1..end]
+            .split('.')
+            .map(|part| part.trim())
+            .collect::<Vec<_>>()
+            .join(".");
+        if let Some(first) = seen.insert(header.clone(), i + 1) {
+            return Some((header, first, i + 1));
+        }
+    }
+    None
+}
+
+/// Resolves the `path` of a path dependency against the directory of the
+/// manifest declaring it.
+///
+/// The result is absolute and lexically normalized: `.` components, `..`
+/// components and trailing separators are collapsed, so every spelling of the
+/// same directory produces the same path, and therefore the same `SourceId`.
+pub(crate) fn resolve_path_dependency(root: &Path, path: &str) -> PathBuf {
+    util::normalize_path(&root.join(path))
 }
 
 type TomlLibTarget = TomlTarget;
@@ -249,7 +656,7 @@ impl<'de> de::Deserialize<'de> for TomlDependency {
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct DetailedTomlDependency {
-    version: Option<String>,
+    version: Option<StringOrVecString>,
     registry: Option<String>,
     /// The URL of the `registry` field.
     /// This is an internal implementation detail. When Cargo creates a
@@ -260,6 +667,9 @@ pub struct DetailedTomlDependency {
     registry_index: Option<String>,
     path: Option<String>,
     git: Option<String>,
+    /// The requirement to publish a `git` dependency with, when the version
+    /// it is released under on the registry differs from `version`.
+    registry_version: Option<String>,
     branch: Option<String>,
     tag: Option<String>,
     rev: Option<String>,
@@ -270,6 +680,16 @@ pub struct DetailedTomlDependency {
     default_features2: Option<bool>,
     package: Option<String>,
     public: Option<bool>,
+    /// The artifact kinds requested by an artifact dependency.
+    artifact: Option<StringOrVec>,
+    /// Whether an artifact dependency also makes the library available.
+    lib: Option<bool>,
+    /// The target triple to build the artifacts of an artifact dependency for.
+    target: Option<String>,
+    /// The lowest ABI version of the dependency that the dependent can use.
+    minimum_abi_version: Option<String>,
+    /// The SHA-256 checksum the registry package must have.
+    checksum: Option<String>,
 }
 
 /// This type is used to deserialize `Cargo.toml` files.
@@ -298,8 +718,12 @@ pub struct TomlManifest {
     patch: Option<BTreeMap<String, BTreeMap<String, TomlDependency>>>,
     workspace: Option<TomlWorkspace>,
     badges: Option<BTreeMap<String, BTreeMap<String, String>>>,
+    lints: Option<TomlLints>,
 }
 
+/// The profiles that Cargo defines without them being in the manifest.
+const BUILTIN_PROFILES: &[&str] = &["dev", "release", "test", "bench", "check", "doc"];
+
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
 pub struct TomlProfiles(BTreeMap<InternedString, TomlProfile>);
 
@@ -314,14 +738,145 @@ impl TomlProfiles {
 
     pub fn validate(&self, features: &Features, warnings: &mut Vec<String>) -> CargoResult<()> {
         for (name, profile) in &self.0 {
-            profile.validate(name, features, warnings)?;
+            profile.validate(name, features, warnings).map_err(|e| {
+                ManifestKeyError::new(e, vec!["profile".to_string(), name.to_string()])
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Checks that every `inherits` names a different profile that is
+    /// built in, defined in this table, or defined in the Cargo config.
+    ///
+    /// Only the profiles of the workspace root are used, so this is called
+    /// for the root manifest rather than for every manifest parsed.
+    pub fn validate_inherits(&self, config: &Config) -> CargoResult<()> {
+        for (name, profile) in &self.0 {
+            let inherits = match profile.inherits {
+                Some(inherits) => inherits,
+                None => continue,
+            };
+            if inherits == *name {
+                bail!(
+                    "profile `{}` inherits from itself; \
+                     `inherits` must name a different profile",
+                    name
+                );
+            }
+            if BUILTIN_PROFILES.contains(&inherits.as_str()) || self.0.contains_key(&inherits) {
+                continue;
+            }
+            // Profiles may also be defined in `.cargo/config`; if reading it
+            // fails, leave the error to profile resolution.
+            let config_profile =
+                config.get::<Option<TomlProfile>>(&format!("profile.{}", inherits));
+            if !matches!(config_profile, Ok(None)) {
+                continue;
+            }
+            let defined: Vec<String> = BUILTIN_PROFILES
+                .iter()
+                .copied()
+                .chain(
+                    self.0
+                        .keys()
+                        .map(|name| name.as_str())
+                        .filter(|name| !BUILTIN_PROFILES.contains(name)),
+                )
+                .map(|name| format!("`{}`", name))
+                .collect();
+            bail!(
+                "profile `{}` inherits from `{}`, but that profile is not defined\n\
+                 \n\
+                 Defined profiles: {}",
+                name,
+                inherits,
+                defined.join(", ")
+            );
         }
         Ok(())
     }
+
+    /// Returns a copy without the profiles that set nothing, or `None` if no
+    /// profile is left.
+    pub fn prune_empty(&self) -> Option<TomlProfiles> {
+        let profiles: BTreeMap<_, _> = self
+            .0
+            .iter()
+            .filter(|(_, profile)| !profile.is_empty())
+            .map(|(name, profile)| (*name, profile.clone()))
+            .collect();
+        if profiles.is_empty() {
+            None
+        } else {
+            Some(TomlProfiles(profiles))
+        }
+    }
+
+    /// Returns the named profile with its `inherits` chain merged in, or
+    /// `None` if the profile is not defined in this table.
+    ///
+    /// Built-in profiles that are not defined here only contribute their
+    /// implicit `inherits` (for example `test` inherits from `dev`), and
+    /// Cargo's own defaults for `dev` and `release` are not included.
+    pub fn resolve(&self, name: &str) -> CargoResult<Option<TomlProfile>> {
+        let name = InternedString::new(name);
+        if !self.0.contains_key(&name) {
+            return Ok(None);
+        }
+        let mut set = HashSet::new();
+        set.insert(name);
+        self.resolve_chain(name, &mut set).map(Some)
+    }
+
+    fn resolve_chain(
+        &self,
+        name: InternedString,
+        set: &mut HashSet<InternedString>,
+    ) -> CargoResult<TomlProfile> {
+        let default = TomlProfile::default();
+        let profile = self.0.get(&name).unwrap_or(&default);
+        let inherits = match (profile.inherits, name.as_str()) {
+            (Some(inherits), _) => Some(inherits),
+            (None, "dev") | (None, "release") => None,
+            (None, "test") | (None, "check") | (None, "doc") => Some(InternedString::new("dev")),
+            (None, "bench") => Some(InternedString::new("release")),
+            (None, _) => bail!(
+                "profile `{}` is missing an `inherits` directive \
+                 (`inherits` is required for all profiles except `dev` or `release`)",
+                name
+            ),
+        };
+        let inherits = match inherits {
+            Some(inherits) => inherits,
+            None => return Ok(profile.clone()),
+        };
+        if !set.insert(inherits) {
+            bail!(
+                "profile inheritance loop detected with profile `{}` inheriting `{}`",
+                name,
+                inherits
+            );
+        }
+        if !BUILTIN_PROFILES.contains(&inherits.as_str()) && !self.0.contains_key(&inherits) {
+            bail!(
+                "profile `{}` inherits from `{}`, but that profile is not defined",
+                name,
+                inherits
+            );
+        }
+        let mut parent = self.resolve_chain(inherits, set)?;
+        parent.merge_inheriting(profile);
+        Ok(parent)
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct TomlOptLevel(pub String);
+pub struct TomlOptLevel {
+    pub level: String,
+    /// Whether a numeric level was written as a quoted string, like `"2"`,
+    /// so that it is serialized back the same way.
+    pub was_string: bool,
+}
 
 impl<'de> de::Deserialize<'de> for TomlOptLevel {
     fn deserialize<D>(d: D) -> Result<TomlOptLevel, D::Error>
@@ -341,15 +896,21 @@ impl<'de> de::Deserialize<'de> for TomlOptLevel {
             where
                 E: de::Error,
             {
-                Ok(TomlOptLevel(value.to_string()))
+                Ok(TomlOptLevel {
+                    level: value.to_string(),
+                    was_string: false,
+                })
             }
 
             fn visit_str<E>(self, value: &str) -> Result<TomlOptLevel, E>
             where
                 E: de::Error,
             {
-                if value == "s" || value == "z" {
-                    Ok(TomlOptLevel(value.to_string()))
+                if value == "s" || value == "z" || value.parse::<u32>().is_ok() {
+                    Ok(TomlOptLevel {
+                        level: value.to_string(),
+                        was_string: true,
+                    })
                 } else {
                     Err(E::custom(format!(
                         "must be an integer, `z`, or `s`, \
@@ -364,14 +925,42 @@ impl<'de> de::Deserialize<'de> for TomlOptLevel {
     }
 }
 
+/// Displays the level as it would be written in `Cargo.toml`, such as `2`
+/// or `"s"`.
+impl fmt::Display for TomlOptLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.level.parse::<u32>() {
+            Ok(n) if !self.was_string => write!(f, "{}", n),
+            _ => fmt_toml_str(&self.level, f),
+        }
+    }
+}
+
+/// Writes `s` as a quoted TOML string, for use in error messages.
+fn fmt_toml_str(s: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", toml::Value::String(s.to_string()))
+}
+
+/// Writes `list` as an inline TOML array of strings.
+fn fmt_toml_str_list(list: &[String], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("[")?;
+    for (i, s) in list.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        fmt_toml_str(s, f)?;
+    }
+    f.write_str("]")
+}
+
 impl ser::Serialize for TomlOptLevel {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
     {
-        match self.0.parse::<u32>() {
-            Ok(n) => n.serialize(serializer),
-            Err(_) => self.0.serialize(serializer),
+        match self.level.parse::<u32>() {
+            Ok(n) if !self.was_string => n.serialize(serializer),
+            _ => self.level.serialize(serializer),
         }
     }
 }
@@ -423,6 +1012,15 @@ impl<'de> de::Deserialize<'de> for U32OrBool {
     }
 }
 
+impl fmt::Display for U32OrBool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            U32OrBool::U32(n) => write!(f, "{}", n),
+            U32OrBool::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Clone, Debug, Default, Eq, PartialEq)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct TomlProfile {
@@ -440,7 +1038,23 @@ pub struct TomlProfile {
     pub build_override: Option<Box<TomlProfile>>,
     pub dir_name: Option<InternedString>,
     pub inherits: Option<InternedString>,
+    pub merge_policy: Option<MergePolicy>,
     pub strip: Option<StringOrBool>,
+    pub trim_paths: Option<StringOrVec>,
+    /// Shorthand for the `opt-level` of `[profile.<name>.package."*"]`.
+    pub package_defaults: Option<TomlOptLevel>,
+    pub self_profile: Option<bool>,
+}
+
+/// How a profile's fields are combined with the profile it `inherits` from.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergePolicy {
+    /// Fields set in the inheriting profile replace the parent's (the default).
+    Override,
+    /// Fields set in the inheriting profile are only used where the parent
+    /// leaves them unset.
+    IfUnset,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -488,14 +1102,27 @@ impl TomlProfile {
             warnings.push("use `[profile.dev]` to configure debug builds".to_string());
         }
 
+        self.validate_codegen_units(&format!("profile.{}", name), warnings)?;
+
         if let Some(ref profile) = self.build_override {
             features.require(Feature::profile_overrides())?;
-            profile.validate_override("build-override")?;
+            let table = format!("profile.{}.build-override", name);
+            profile.validate_override("build-override", &table)?;
+            profile.validate_codegen_units(&table, warnings)?;
         }
         if let Some(ref packages) = self.package {
             features.require(Feature::profile_overrides())?;
-            for profile in packages.values() {
-                profile.validate_override("package")?;
+            for (spec, profile) in packages {
+                let spec = match spec {
+                    ProfilePackageSpec::Spec(spec) => spec.to_string(),
+                    ProfilePackageSpec::All => "*".to_string(),
+                };
+                let table = format!("profile.{}.package.\"{}\"", name, spec);
+                profile.validate_override("package", &table)?;
+                profile.validate_codegen_units(&table, warnings)?;
+                if profile.strip.is_some() {
+                    features.require(Feature::strip())?;
+                }
             }
         }
 
@@ -519,6 +1146,10 @@ impl TomlProfile {
             features.require(Feature::named_profiles())?;
         }
 
+        if self.merge_policy.is_some() {
+            features.require(Feature::named_profiles())?;
+        }
+
         // `dir-name` validation
         match &self.dir_name {
             None => {}
@@ -560,6 +1191,74 @@ impl TomlProfile {
         if self.strip.is_some() {
             features.require(Feature::strip())?;
         }
+
+        if self.package_defaults.is_some() {
+            features.require(Feature::profile_package_defaults())?;
+        }
+
+        let mut overrides = self
+            .build_override
+            .iter()
+            .map(Box::as_ref)
+            .chain(self.package.iter().flat_map(|p| p.values()));
+        if self.self_profile.is_some() || overrides.any(|p| p.self_profile.is_some()) {
+            features.require(Feature::profile_self_profile())?;
+        }
+
+        self.validate_trim_paths(features)?;
+        if let Some(ref profile) = self.build_override {
+            profile.validate_trim_paths(features)?;
+        }
+        if let Some(ref packages) = self.package {
+            for profile in packages.values() {
+                profile.validate_trim_paths(features)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that every `trim-paths` entry is either one of the known
+    /// keywords or looks like a path.
+    fn validate_trim_paths(&self, features: &Features) -> CargoResult<()> {
+        let trim_paths = match &self.trim_paths {
+            Some(trim_paths) => trim_paths,
+            None => return Ok(()),
+        };
+        features.require(Feature::trim_paths())?;
+        for value in trim_paths {
+            match value.as_str() {
+                "none" | "all" if trim_paths.len() > 1 => bail!(
+                    "`trim-paths` value `{}` cannot be combined with other values",
+                    value
+                ),
+                "none" | "all" | "object" | "diagnostics" => {}
+                v if v.starts_with('.') || v.contains('/') || v.contains('\\') => {}
+                v => bail!(
+                    "`trim-paths` setting of `{}` is not a valid setting, \
+                     must be `none`, `all`, `object`, `diagnostics`, or a path",
+                    v
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that `codegen-units` is in a useful range. Zero is rejected by
+    /// rustc, and very large values are allowed but only warned about.
+    fn validate_codegen_units(&self, table: &str, warnings: &mut Vec<String>) -> CargoResult<()> {
+        match self.codegen_units {
+            Some(0) => bail!(
+                "`codegen-units` in `{}` must be at least 1, but is 0",
+                table
+            ),
+            Some(units) if units > 256 => warnings.push(format!(
+                "`codegen-units` in `{}` is {}; values above 256 rarely make \
+                 builds faster and can make the generated code slower \
+                 (this is only a warning)",
+                table, units
+            )),
+            _ => {}
+        }
         Ok(())
     }
 
@@ -592,25 +1291,63 @@ impl TomlProfile {
         Ok(())
     }
 
-    fn validate_override(&self, which: &str) -> CargoResult<()> {
+    /// Validates a `build-override` or `package` profile. `table` is the
+    /// path of the table being validated, like `profile.dev.package."bar"`,
+    /// and is included in errors so the offending table can be found.
+    fn validate_override(&self, which: &str, table: &str) -> CargoResult<()> {
         if self.package.is_some() {
             bail!("package-specific profiles cannot be nested");
         }
         if self.build_override.is_some() {
             bail!("build-override profiles cannot be nested");
         }
-        if self.panic.is_some() {
-            bail!("`panic` may not be specified in a `{}` profile", which)
-        }
-        if self.lto.is_some() {
-            bail!("`lto` may not be specified in a `{}` profile", which)
-        }
-        if self.rpath.is_some() {
-            bail!("`rpath` may not be specified in a `{}` profile", which)
+        for (key, is_set) in &[
+            ("panic", self.panic.is_some()),
+            ("lto", self.lto.is_some()),
+            ("rpath", self.rpath.is_some()),
+            ("merge-policy", self.merge_policy.is_some()),
+            ("package-defaults", self.package_defaults.is_some()),
+            // Stripping happens when the final artifact is linked, which
+            // doesn't apply to build scripts and proc-macros built for the
+            // host. A package override can still strip a single crate.
+            ("strip", which == "build-override" && self.strip.is_some()),
+        ] {
+            if *is_set {
+                bail!(
+                    "`{}` may not be specified in a `{}` profile, found in `[{}]`",
+                    key,
+                    which,
+                    table
+                )
+            }
         }
         Ok(())
     }
 
+    /// Returns `true` if no field of the profile is set.
+    pub fn is_empty(&self) -> bool {
+        *self == TomlProfile::default()
+    }
+
+    /// Merges `profile`, which inherits from self, into self according to
+    /// the `merge-policy` of `profile`.
+    pub fn merge_inheriting(&mut self, profile: &TomlProfile) {
+        match profile.merge_policy {
+            Some(MergePolicy::IfUnset) => {
+                // Start from the inheriting profile and let everything the
+                // parent chain sets take precedence, keeping the inheriting
+                // profile's identity fields.
+                let mut merged = profile.clone();
+                merged.merge(self);
+                merged.inherits = profile.inherits;
+                merged.dir_name = profile.dir_name;
+                merged.merge_policy = profile.merge_policy;
+                *self = merged;
+            }
+            Some(MergePolicy::Override) | None => self.merge(profile),
+        }
+    }
+
     /// Overwrite self's values with the given profile.
     pub fn merge(&mut self, profile: &TomlProfile) {
         if let Some(v) = &profile.opt_level {
@@ -684,9 +1421,25 @@ impl TomlProfile {
             self.dir_name = Some(*v);
         }
 
+        if let Some(v) = profile.merge_policy {
+            self.merge_policy = Some(v);
+        }
+
         if let Some(v) = &profile.strip {
             self.strip = Some(v.clone());
         }
+
+        if let Some(v) = &profile.trim_paths {
+            self.trim_paths = Some(v.clone());
+        }
+
+        if let Some(v) = &profile.package_defaults {
+            self.package_defaults = Some(v.clone());
+        }
+
+        if let Some(v) = profile.self_profile {
+            self.self_profile = Some(v);
+        }
     }
 }
 
@@ -727,6 +1480,147 @@ impl<'de> de::Deserialize<'de> for StringOrVec {
     }
 }
 
+impl fmt::Display for StringOrVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_toml_str_list(&self.0, f)
+    }
+}
+
+impl StringOrVec {
+    pub fn as_slice(&self) -> &[String] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl std::ops::Deref for StringOrVec {
+    type Target = [String];
+
+    fn deref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl From<Vec<String>> for StringOrVec {
+    fn from(v: Vec<String>) -> StringOrVec {
+        StringOrVec(v)
+    }
+}
+
+impl From<StringOrVec> for Vec<String> {
+    fn from(v: StringOrVec) -> Vec<String> {
+        v.0
+    }
+}
+
+impl IntoIterator for StringOrVec {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a StringOrVec {
+    type Item = &'a String;
+    type IntoIter = std::slice::Iter<'a, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// A dependency `version`: a single requirement, or a list of alternative
+/// requirements of which any may be satisfied.
+#[derive(Clone, Debug, Serialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum StringOrVecString {
+    String(String),
+    VecString(Vec<String>),
+}
+
+impl StringOrVecString {
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            StringOrVecString::String(s) => std::slice::from_ref(s),
+            StringOrVecString::VecString(v) => v,
+        }
+    }
+
+    /// Combines the alternatives into a single requirement string.
+    ///
+    /// The `semver` crate has no "or" operator, so a list is only accepted
+    /// when it boils down to a single distinct requirement.
+    fn to_requirement(&self, name_in_toml: &str) -> CargoResult<String> {
+        let mut reqs: Vec<&str> = self.as_slice().iter().map(|s| s.trim()).collect();
+        reqs.sort_unstable();
+        reqs.dedup();
+        match reqs[..] {
+            [] => bail!(
+                "dependency ({}) has an empty `version` list, \
+                 expected at least one version requirement",
+                name_in_toml
+            ),
+            [req] => Ok(req.to_string()),
+            _ => bail!(
+                "dependency ({}) lists alternative version requirements `{}`, \
+                 but alternatives cannot be combined into a single requirement; \
+                 use one requirement that covers all of them instead",
+                name_in_toml,
+                reqs.join("`, `")
+            ),
+        }
+    }
+}
+
+impl From<String> for StringOrVecString {
+    fn from(s: String) -> Self {
+        StringOrVecString::String(s)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for StringOrVecString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = StringOrVecString;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a version requirement or a list of version requirements")
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(StringOrVecString::String(s.to_string()))
+            }
+
+            fn visit_seq<V>(self, v: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                let seq = de::value::SeqAccessDeserializer::new(v);
+                Vec::deserialize(seq).map(StringOrVecString::VecString)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Eq, PartialEq)]
 #[serde(untagged)]
 pub enum StringOrBool {
@@ -767,6 +1661,15 @@ impl<'de> de::Deserialize<'de> for StringOrBool {
     }
 }
 
+impl fmt::Display for StringOrBool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StringOrBool::String(s) => fmt_toml_str(s, f),
+            StringOrBool::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
 #[derive(PartialEq, Clone, Debug, Serialize)]
 #[serde(untagged)]
 pub enum VecStringOrBool {
@@ -808,6 +1711,135 @@ impl<'de> de::Deserialize<'de> for VecStringOrBool {
     }
 }
 
+impl fmt::Display for VecStringOrBool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VecStringOrBool::VecString(v) => fmt_toml_str_list(v, f),
+            VecStringOrBool::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+/// The `package.publish` key.
+#[derive(PartialEq, Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum TomlPublish {
+    Bool(bool),
+    Registries(Vec<String>),
+    /// A single registry name written without the surrounding array, which
+    /// is accepted as if it were a one-element array.
+    Registry(String),
+}
+
+impl TomlPublish {
+    /// The registries publishing is restricted to, where `None` means any
+    /// registry and an empty list means publishing is disabled.
+    pub fn registries(&self) -> Option<Vec<String>> {
+        match self {
+            TomlPublish::Bool(true) => None,
+            TomlPublish::Bool(false) => Some(Vec::new()),
+            TomlPublish::Registries(registries) => Some(registries.clone()),
+            TomlPublish::Registry(registry) => Some(vec![registry.clone()]),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for TomlPublish {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = TomlPublish;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a boolean or an array of registry names")
+            }
+
+            fn visit_seq<V>(self, mut v: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                let mut registries = Vec::new();
+                while let Some(value) = v.next_element::<toml::Value>()? {
+                    match value {
+                        toml::Value::String(registry) => registries.push(registry),
+                        toml::Value::Boolean(false) => {
+                            return Err(de::Error::custom(
+                                "`publish` cannot mix registry names with `false`; \
+                                 use `publish = false` to prevent publishing",
+                            ))
+                        }
+                        value => {
+                            return Err(de::Error::custom(format_args!(
+                                "`publish` must be an array of registry names, \
+                                 but it contains the {} `{}`",
+                                value.type_str(),
+                                value
+                            )))
+                        }
+                    }
+                }
+                Ok(TomlPublish::Registries(registries))
+            }
+
+            fn visit_bool<E>(self, b: bool) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(TomlPublish::Bool(b))
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(TomlPublish::Registry(s.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// Deserializes an `edition` key, with a hint for the common mistake of
+/// writing the edition as an integer instead of a string.
+fn deserialize_edition<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    struct Visitor;
+
+    impl<'de> de::Visitor<'de> for Visitor {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an edition string like \"2018\"")
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(s.to_string())
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Err(E::custom(format!(
+                "editions must be written as strings, e.g. `edition = \"{}\"`",
+                v
+            )))
+        }
+    }
+
+    deserializer.deserialize_any(Visitor).map(Some)
+}
+
 /// Represents the `package`/`project` sections of a `Cargo.toml`.
 ///
 /// Note that the order of the fields matters, since this is the order they
@@ -817,6 +1849,7 @@ impl<'de> de::Deserialize<'de> for VecStringOrBool {
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "kebab-case")]
 pub struct TomlProject {
+    #[serde(default, deserialize_with = "deserialize_edition")]
     edition: Option<String>,
     rust_version: Option<String>,
     name: InternedString,
@@ -825,16 +1858,21 @@ pub struct TomlProject {
     build: Option<StringOrBool>,
     metabuild: Option<StringOrVec>,
     links: Option<String>,
+    links_version: Option<String>,
     exclude: Option<Vec<String>>,
     include: Option<Vec<String>>,
-    publish: Option<VecStringOrBool>,
+    publish: Option<TomlPublish>,
     workspace: Option<String>,
+    standalone: Option<bool>,
     im_a_teapot: Option<bool>,
     autobins: Option<bool>,
     autoexamples: Option<bool>,
     autotests: Option<bool>,
     autobenches: Option<bool>,
     default_run: Option<String>,
+    /// Keys of `[package.metadata]` and `[badges]` to leave out of the
+    /// published manifest.
+    publish_strip_metadata: Option<Vec<String>>,
 
     // Package metadata.
     description: Option<String>,
@@ -846,10 +1884,56 @@ pub struct TomlProject {
     license: Option<String>,
     license_file: Option<String>,
     repository: Option<String>,
+    funding: Option<TomlFunding>,
+    env: Option<BTreeMap<String, TomlEnvSpec>>,
     metadata: Option<toml::Value>,
     resolver: Option<String>,
 }
 
+/// Where to fund a package, declared in the `[package.funding]` table. The
+/// keys follow GitHub's `FUNDING.yml`.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TomlFunding {
+    /// GitHub Sponsors usernames.
+    pub github: Option<StringOrVec>,
+    pub patreon: Option<String>,
+    pub open_collective: Option<String>,
+    pub ko_fi: Option<String>,
+    pub liberapay: Option<String>,
+    /// Other funding URLs.
+    pub custom: Option<StringOrVec>,
+}
+
+impl TomlFunding {
+    fn validate(&self) -> CargoResult<()> {
+        for user in self.github.iter().flatten() {
+            if user.is_empty() || !user.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                bail!(
+                    "invalid GitHub username `{}` in `package.funding.github`, \
+                     usernames may only contain ASCII letters, digits and `-`",
+                    user
+                );
+            }
+        }
+        for custom in self.custom.iter().flatten() {
+            Url::parse(custom)
+                .chain_err(|| format!("invalid URL `{}` in `package.funding.custom`", custom))?;
+        }
+        Ok(())
+    }
+}
+
+/// An environment variable a package's build script depends on, declared in
+/// the `[package.env]` table.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct TomlEnvSpec {
+    /// Whether the build script fails without the variable.
+    pub required: Option<bool>,
+    pub description: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct TomlWorkspace {
     members: Option<Vec<String>>,
@@ -858,6 +1942,156 @@ pub struct TomlWorkspace {
     exclude: Option<Vec<String>>,
     metadata: Option<toml::Value>,
     resolver: Option<String>,
+    lints: Option<TomlLints>,
+    limits: Option<TomlWorkspaceLimits>,
+}
+
+/// The `[workspace.limits]` table, budgets that every workspace member's
+/// manifest is checked against.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct TomlWorkspaceLimits {
+    /// The most normal and build dependencies a member may declare.
+    pub max_direct_dependencies: Option<usize>,
+    /// The most dev-dependencies a member may declare.
+    pub max_dev_dependencies: Option<usize>,
+    /// The most entries a member's `[features]` table may have.
+    pub max_features: Option<usize>,
+    /// Whether exceeding a limit is an error rather than a warning.
+    pub deny: Option<bool>,
+}
+
+/// The `[lints]` and `[workspace.lints]` tables, mapping each tool (`rust`,
+/// `clippy`, `rustdoc`) to the levels of its lints.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct TomlLints(BTreeMap<String, BTreeMap<String, TomlLintConfig>>);
+
+impl TomlLints {
+    /// Checks that every entry is of the form `<tool>::<name>`, where neither
+    /// part is empty or contains a path separator of its own.
+    fn validate(&self, table: &str) -> CargoResult<()> {
+        fn is_valid_name(name: &str) -> bool {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        }
+
+        for (tool, lints) in &self.0 {
+            if !is_valid_name(tool) {
+                bail!("invalid lint tool `{}` in `[{}]`", tool, table);
+            }
+            for name in lints.keys() {
+                if !is_valid_name(name) {
+                    bail!(
+                        "invalid lint `{}::{}` in `[{}.{}]`, lint names are \
+                         written without a tool prefix, like `{}::dead_code` \
+                         is written as `dead_code` in `[{}.{}]`",
+                        tool,
+                        name,
+                        table,
+                        tool,
+                        tool,
+                        table,
+                        tool
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The configuration of a single lint, either just a level or a table with a
+/// `level` and an optional `priority`.
+#[derive(Serialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum TomlLintConfig {
+    Level(TomlLintLevel),
+    Detailed {
+        level: TomlLintLevel,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        priority: Option<i8>,
+    },
+}
+
+impl<'de> de::Deserialize<'de> for TomlLintConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = TomlLintConfig;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a lint level or a table with a `level` key")
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                s.parse().map(TomlLintConfig::Level).map_err(E::custom)
+            }
+
+            fn visit_map<V>(self, map: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                #[derive(Deserialize)]
+                #[serde(deny_unknown_fields)]
+                struct Detailed {
+                    level: TomlLintLevel,
+                    priority: Option<i8>,
+                }
+
+                let mvd = de::value::MapAccessDeserializer::new(map);
+                let Detailed { level, priority } = Detailed::deserialize(mvd)?;
+                Ok(TomlLintConfig::Detailed { level, priority })
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TomlLintLevel {
+    Forbid,
+    Deny,
+    Warn,
+    Allow,
+}
+
+impl str::FromStr for TomlLintLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "forbid" => Ok(TomlLintLevel::Forbid),
+            "deny" => Ok(TomlLintLevel::Deny),
+            "warn" => Ok(TomlLintLevel::Warn),
+            "allow" => Ok(TomlLintLevel::Allow),
+            _ => bail!(
+                "`{}` is not a valid lint level, expected one of \
+                 `forbid`, `deny`, `warn` or `allow`",
+                s
+            ),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for TomlLintLevel {
+    fn deserialize<D>(d: D) -> Result<TomlLintLevel, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(d)?;
+        s.parse().map_err(de::Error::custom)
+    }
 }
 
 impl TomlProject {
@@ -873,6 +2107,7 @@ struct Context<'a, 'b> {
     nested_paths: &'a mut Vec<PathBuf>,
     config: &'b Config,
     warnings: &'a mut Vec<String>,
+    deprecated_syntax: &'a mut DeprecatedSyntax,
     platform: Option<Platform>,
     root: &'a Path,
     features: &'a Features,
@@ -896,6 +2131,19 @@ impl TomlManifest {
             .clone();
         package.workspace = None;
         package.resolver = ws.resolve_behavior().to_manifest();
+        // Older versions of Cargo only understand the array form.
+        if let Some(TomlPublish::Registry(registry)) = &package.publish {
+            package.publish = Some(TomlPublish::Registries(vec![registry.clone()]));
+        }
+        let mut badges = self.badges.clone();
+        for key in package.publish_strip_metadata.take().into_iter().flatten() {
+            if let Some(toml::Value::Table(metadata)) = &mut package.metadata {
+                metadata.remove(&key);
+            }
+            if let Some(badges) = &mut badges {
+                badges.remove(&key);
+            }
+        }
         if let Some(license_file) = &package.license_file {
             let license_path = Path::new(&license_file);
             let abs_license_path = paths::normalize_path(&package_root.join(license_path));
@@ -912,11 +2160,42 @@ impl TomlManifest {
                 );
             }
         }
-        let all = |_d: &TomlDependency| true;
+        // Dev-dependencies without a version are dropped below, but the
+        // other kinds are kept with their `path` stripped, which would leave
+        // them without any source at all.
+        let mut published_tables = vec![
+            self.dependencies.as_ref(),
+            self.build_dependencies
+                .as_ref()
+                .or_else(|| self.build_dependencies2.as_ref()),
+        ];
+        for platform in self.target.iter().flat_map(BTreeMap::values) {
+            published_tables.push(platform.dependencies.as_ref());
+            published_tables.push(
+                platform
+                    .build_dependencies
+                    .as_ref()
+                    .or_else(|| platform.build_dependencies2.as_ref()),
+            );
+        }
+        for (name, dep) in published_tables.into_iter().flatten().flatten() {
+            if let TomlDependency::Detailed(d) = dep {
+                if d.path.is_some() && d.version.is_none() {
+                    bail!(
+                        "all dependencies must have a version specified when publishing; \
+                         dependency `{}` has only a path",
+                        name
+                    );
+                }
+            }
+        }
+
+        let all = |_name: &str, _d: &TomlDependency| true;
+        let version_specified = |_name: &str, d: &TomlDependency| d.is_version_specified();
         return Ok(TomlManifest {
             package: Some(package),
             project: None,
-            profile: self.profile.clone(),
+            profile: self.profile.as_ref().and_then(TomlProfiles::prune_empty),
             lib: self.lib.clone(),
             bin: self.bin.clone(),
             example: self.example.clone(),
@@ -928,7 +2207,7 @@ impl TomlManifest {
                 self.dev_dependencies
                     .as_ref()
                     .or_else(|| self.dev_dependencies2.as_ref()),
-                TomlDependency::is_version_specified,
+                version_specified,
             )?,
             dev_dependencies2: None,
             build_dependencies: map_deps(
@@ -953,7 +2232,7 @@ impl TomlManifest {
                                     v.dev_dependencies
                                         .as_ref()
                                         .or_else(|| v.dev_dependencies2.as_ref()),
-                                    TomlDependency::is_version_specified,
+                                    version_specified,
                                 )?,
                                 dev_dependencies2: None,
                                 build_dependencies: map_deps(
@@ -976,51 +2255,10 @@ impl TomlManifest {
             replace: None,
             patch: None,
             workspace: None,
-            badges: self.badges.clone(),
+            badges,
+            lints: self.lints.clone(),
             cargo_features: self.cargo_features.clone(),
         });
-
-        fn map_deps(
-            config: &Config,
-            deps: Option<&BTreeMap<String, TomlDependency>>,
-            filter: impl Fn(&TomlDependency) -> bool,
-        ) -> CargoResult<Option<BTreeMap<String, TomlDependency>>> {
-            let deps = match deps {
-                Some(deps) => deps,
-                None => return Ok(None),
-            };
-            let deps = deps
-                .iter()
-                .filter(|(_k, v)| filter(v))
-                .map(|(k, v)| Ok((k.clone(), map_dependency(config, v)?)))
-                .collect::<CargoResult<BTreeMap<_, _>>>()?;
-            Ok(Some(deps))
-        }
-
-        fn map_dependency(config: &Config, dep: &TomlDependency) -> CargoResult<TomlDependency> {
-            match dep {
-                TomlDependency::Detailed(d) => {
-                    let mut d = d.clone();
-                    // Path dependencies become crates.io deps.
-                    d.path.take();
-                    // Same with git dependencies.
-                    d.git.take();
-                    d.branch.take();
-                    d.tag.take();
-                    d.rev.take();
-                    // registry specifications are elaborated to the index URL
-                    if let Some(registry) = d.registry.take() {
-                        let src = SourceId::alt_registry(config, &registry)?;
-                        d.registry_index = Some(src.url().to_string());
-                    }
-                    Ok(TomlDependency::Detailed(d))
-                }
-                TomlDependency::Simple(s) => Ok(TomlDependency::Detailed(DetailedTomlDependency {
-                    version: Some(s.clone()),
-                    ..Default::default()
-                })),
-            }
-        }
     }
 
     pub fn to_real_manifest(
@@ -1030,14 +2268,20 @@ impl TomlManifest {
         config: &Config,
     ) -> CargoResult<(Manifest, Vec<PathBuf>)> {
         let mut nested_paths = vec![];
-        let mut warnings = vec![];
+        let mut warnings = Warnings::new();
         let mut errors = vec![];
+        let mut deprecated_syntax = DeprecatedSyntax::default();
 
         // Parse features first so they will be available when parsing other parts of the TOML.
         let empty = Vec::new();
         let cargo_features = me.cargo_features.as_ref().unwrap_or(&empty);
-        let features = Features::new(cargo_features, config, &mut warnings)?;
+        let mut feature_warnings = vec![];
+        let features = Features::new(cargo_features, config, &mut feature_warnings)?;
+        warnings.extend(WarningCategory::Deprecated, feature_warnings);
 
+        if me.project.is_some() {
+            deprecated_syntax.insert(DeprecatedSyntax::PROJECT_TABLE);
+        }
         let project = me.project.as_ref().or_else(|| me.package.as_ref());
         let project = project.ok_or_else(|| anyhow!("no `package` section found"))?;
 
@@ -1047,9 +2291,14 @@ impl TomlManifest {
         }
 
         validate_package_name(package_name, "package name", "")?;
+        let mut name_warnings = vec![];
+        warn_on_unusual_package_name(package_name, &mut name_warnings);
+        warnings.extend(WarningCategory::Metadata, name_warnings);
 
         let pkgid = project.to_package_id(source_id)?;
 
+        let platforms = me.validate_platform_targets()?;
+
         let edition = if let Some(ref edition) = project.edition {
             features
                 .require(Feature::edition())
@@ -1088,7 +2337,7 @@ impl TomlManifest {
                         `cargo-features = [\"rust-version\"]` to enable this feature",
                     );
                 }
-                warnings.push(msg);
+                warnings.push(WarningCategory::Metadata, msg);
                 None
             } else {
                 let req = match semver::VersionReq::parse(rust_version) {
@@ -1119,6 +2368,10 @@ impl TomlManifest {
             features.require(Feature::metabuild())?;
         }
 
+        if project.env.is_some() {
+            features.require(Feature::package_env())?;
+        }
+
         if project.resolver.is_some()
             || me
                 .workspace
@@ -1127,12 +2380,30 @@ impl TomlManifest {
         {
             features.require(Feature::resolver())?;
         }
-        let resolve_behavior = match (
+        if me
+            .workspace
+            .as_ref()
+            .map_or(false, |ws| ws.limits.is_some())
+        {
+            features.require(Feature::workspace_limits())?;
+        }
+        me.validate_lints(&features)?;
+        let (resolve_behavior, resolver_source) = match (
             project.resolver.as_ref(),
             me.workspace.as_ref().and_then(|ws| ws.resolver.as_ref()),
         ) {
-            (None, None) => None,
-            (Some(s), None) | (None, Some(s)) => Some(ResolveBehavior::from_manifest(s)?),
+            (None, None) if edition >= Edition::Edition2021 => {
+                (None, ResolverSource::EditionDefault)
+            }
+            (None, None) => (None, ResolverSource::Absent),
+            (Some(s), None) => (
+                Some(ResolveBehavior::from_manifest(s)?),
+                ResolverSource::PackageField,
+            ),
+            (None, Some(s)) => (
+                Some(ResolveBehavior::from_manifest(s)?),
+                ResolverSource::WorkspaceField,
+            ),
             (Some(_), Some(_)) => {
                 bail!("cannot specify `resolver` field in both `[workspace]` and `[package]`")
             }
@@ -1141,6 +2412,7 @@ impl TomlManifest {
         // If we have no lib at all, use the inferred lib, if available.
         // If we have a lib with a path, we're done.
         // If we have a lib with no path, use the inferred lib or else the package name.
+        let mut target_warnings = vec![];
         let targets = targets(
             &features,
             me,
@@ -1149,20 +2421,49 @@ impl TomlManifest {
             edition,
             &project.build,
             &project.metabuild,
-            &mut warnings,
+            &mut target_warnings,
+            &mut deprecated_syntax,
             &mut errors,
         )?;
+        warnings.extend(WarningCategory::Target, target_warnings);
 
         if targets.is_empty() {
             debug!("manifest has no build targets");
         }
 
         if let Err(e) = unique_build_targets(&targets, package_root) {
-            warnings.push(format!(
-                "file found to be present in multiple \
+            warnings.push(
+                WarningCategory::Target,
+                format!(
+                    "file found to be present in multiple \
                  build targets: {}",
-                e
-            ));
+                    e
+                ),
+            );
+        }
+
+        for target in targets.iter().filter(|t| t.edition() != edition) {
+            warnings.push(
+                WarningCategory::Target,
+                format!(
+                    "{} target uses edition {}, but the package uses edition {}; \
+                 mixing editions within a package is easy to miss, so make sure \
+                 this is intentional and documented",
+                    target.description_named(),
+                    target.edition(),
+                    edition
+                ),
+            );
+        }
+
+        if let Some(env) = &project.env {
+            if !env.is_empty() && !targets.iter().any(|t| t.is_custom_build()) {
+                bail!(
+                    "package `{}` declares environment variables in `package.env` \
+                     but does not have a custom build script",
+                    pkgid
+                )
+            }
         }
 
         if let Some(links) = &project.links {
@@ -1175,10 +2476,29 @@ impl TomlManifest {
                 )
             }
         }
+        let links_key = match (&project.links, &project.links_version) {
+            (_, None) => project.links.clone(),
+            (None, Some(_)) => {
+                features.require(Feature::links_version())?;
+                bail!("`package.links-version` can only be used together with `package.links`")
+            }
+            (Some(links), Some(version)) => {
+                features.require(Feature::links_version())?;
+                let version = semver::Version::parse(version.trim()).chain_err(|| {
+                    format!(
+                        "failed to parse `package.links-version`, \
+                         expected a version like `1.1.0`, found `{}`",
+                        version
+                    )
+                })?;
+                Some(links_key(links, &version))
+            }
+        };
 
         let mut deps = Vec::new();
         let replace;
         let patch;
+        let mut dep_warnings = vec![];
 
         {
             let mut cx = Context {
@@ -1187,7 +2507,8 @@ impl TomlManifest {
                 source_id,
                 nested_paths: &mut nested_paths,
                 config,
-                warnings: &mut warnings,
+                warnings: &mut dep_warnings,
+                deprecated_syntax: &mut deprecated_syntax,
                 features: &features,
                 platform: None,
                 root: package_root,
@@ -1197,13 +2518,18 @@ impl TomlManifest {
                 cx: &mut Context<'_, '_>,
                 new_deps: Option<&BTreeMap<String, TomlDependency>>,
                 kind: Option<DepKind>,
+                table: &[&str],
             ) -> CargoResult<()> {
                 let dependencies = match new_deps {
                     Some(dependencies) => dependencies,
                     None => return Ok(()),
                 };
                 for (n, v) in dependencies.iter() {
-                    let dep = v.to_dependency(n, cx, kind)?;
+                    let dep = v.to_dependency(n, cx, kind).map_err(|e| {
+                        let mut key: Vec<_> = table.iter().map(|s| s.to_string()).collect();
+                        key.push(n.clone());
+                        ManifestKeyError::new(e, key)
+                    })?;
                     validate_package_name(dep.name_in_toml().as_str(), "dependency name", "")?;
                     cx.deps.push(dep);
                 }
@@ -1212,53 +2538,117 @@ impl TomlManifest {
             }
 
             // Collect the dependencies.
-            process_dependencies(&mut cx, me.dependencies.as_ref(), None)?;
+            if me.dev_dependencies2.is_some() || me.build_dependencies2.is_some() {
+                cx.deprecated_syntax
+                    .insert(DeprecatedSyntax::UNDERSCORE_KEYS);
+            }
+            process_dependencies(&mut cx, me.dependencies.as_ref(), None, &["dependencies"])?;
             let dev_deps = me
                 .dev_dependencies
                 .as_ref()
                 .or_else(|| me.dev_dependencies2.as_ref());
-            process_dependencies(&mut cx, dev_deps, Some(DepKind::Development))?;
+            process_dependencies(
+                &mut cx,
+                dev_deps,
+                Some(DepKind::Development),
+                &["dev-dependencies"],
+            )?;
             let build_deps = me
                 .build_dependencies
                 .as_ref()
                 .or_else(|| me.build_dependencies2.as_ref());
-            process_dependencies(&mut cx, build_deps, Some(DepKind::Build))?;
-
-            for (name, platform) in me.target.iter().flatten() {
+            process_dependencies(
+                &mut cx,
+                build_deps,
+                Some(DepKind::Build),
+                &["build-dependencies"],
+            )?;
+
+            for (target_key, cfg, platform) in platforms {
                 cx.platform = {
-                    let platform: Platform = name.parse()?;
-                    platform.check_cfg_attributes(&mut cx.warnings);
-                    Some(platform)
+                    cfg.check_cfg_attributes(&mut cx.warnings);
+                    Some(cfg)
                 };
-                process_dependencies(&mut cx, platform.dependencies.as_ref(), None)?;
+                if platform.dev_dependencies2.is_some() || platform.build_dependencies2.is_some() {
+                    cx.deprecated_syntax
+                        .insert(DeprecatedSyntax::UNDERSCORE_KEYS);
+                }
+                process_dependencies(
+                    &mut cx,
+                    platform.dependencies.as_ref(),
+                    None,
+                    &["target", target_key, "dependencies"],
+                )?;
                 let build_deps = platform
                     .build_dependencies
                     .as_ref()
                     .or_else(|| platform.build_dependencies2.as_ref());
-                process_dependencies(&mut cx, build_deps, Some(DepKind::Build))?;
+                process_dependencies(
+                    &mut cx,
+                    build_deps,
+                    Some(DepKind::Build),
+                    &["target", target_key, "build-dependencies"],
+                )?;
                 let dev_deps = platform
                     .dev_dependencies
                     .as_ref()
                     .or_else(|| platform.dev_dependencies2.as_ref());
-                process_dependencies(&mut cx, dev_deps, Some(DepKind::Development))?;
+                process_dependencies(
+                    &mut cx,
+                    dev_deps,
+                    Some(DepKind::Development),
+                    &["target", target_key, "dev-dependencies"],
+                )?;
             }
 
             replace = me.replace(&mut cx)?;
             patch = me.patch(&mut cx)?;
         }
+        warnings.extend(WarningCategory::Dependency, dep_warnings);
 
         {
-            let mut names_sources = BTreeMap::new();
+            let mut names_sources: BTreeMap<_, &Dependency> = BTreeMap::new();
             for dep in &deps {
                 let name = dep.name_in_toml();
-                let prev = names_sources.insert(name.to_string(), dep.source_id());
-                if prev.is_some() && prev != Some(dep.source_id()) {
+                let prev = names_sources.insert(name.to_string(), dep);
+                if let Some(prev) = prev.filter(|prev| prev.source_id() != dep.source_id()) {
                     bail!(
                         "Dependency '{}' has different source paths depending on the build \
                          target. Each dependency must have a single canonical source path \
-                         irrespective of build target.",
-                        name
+                         irrespective of build target.\n\n\
+                         `{}` in {} uses {}\n\
+                         `{}` in {} uses {}",
+                        name,
+                        name,
+                        dependency_table_name(prev),
+                        prev.source_id(),
+                        name,
+                        dependency_table_name(dep),
+                        dep.source_id()
+                    );
+                }
+            }
+
+            // Registries compare package names case-insensitively, so names
+            // that differ only by case likely refer to the same package.
+            let mut lowercase_names: BTreeMap<_, (&str, &Dependency)> = BTreeMap::new();
+            for (name, dep) in &names_sources {
+                let lowercase = name.to_ascii_lowercase();
+                if let Some(&(prev_name, prev)) = lowercase_names.get(&lowercase) {
+                    warnings.push(
+                        WarningCategory::Dependency,
+                        format!(
+                            "dependencies `{}` in {} and `{}` in {} differ only by case; \
+                             registries treat package names case-insensitively, \
+                             so they may refer to the same package",
+                            prev_name,
+                            dependency_table_name(prev),
+                            name,
+                            dependency_table_name(dep)
+                        ),
                     );
+                } else {
+                    lowercase_names.insert(lowercase, (name.as_str(), *dep));
                 }
             }
         }
@@ -1272,16 +2662,25 @@ impl TomlManifest {
             pkgid,
             deps,
             me.features.as_ref().unwrap_or(&empty_features),
-            project.links.as_deref(),
+            links_key.as_deref(),
         )?;
         let unstable = config.cli_unstable();
         summary.unstable_gate(unstable.namespaced_features, unstable.weak_dep_features)?;
 
+        if me
+            .badges
+            .iter()
+            .flat_map(|badges| badges.keys())
+            .any(|badge| badge != "maintenance")
+        {
+            deprecated_syntax.insert(DeprecatedSyntax::BADGES);
+        }
+        let mut readme_warnings = Vec::new();
         let metadata = ManifestMetadata {
             description: project.description.clone(),
             homepage: project.homepage.clone(),
             documentation: project.documentation.clone(),
-            readme: readme_for_project(package_root, project),
+            readme: readme_for_project(package_root, project, &mut readme_warnings),
             authors: project.authors.clone().unwrap_or_default(),
             license: project.license.clone(),
             license_file: project.license_file.clone(),
@@ -1290,15 +2689,53 @@ impl TomlManifest {
             categories: project.categories.clone().unwrap_or_default(),
             badges: me.badges.clone().unwrap_or_default(),
             links: project.links.clone(),
+            funding: project.funding.clone(),
         };
+        warnings.extend(WarningCategory::Metadata, readme_warnings);
+        if let Some(funding) = &project.funding {
+            features.require(Feature::package_funding())?;
+            funding.validate()?;
+        }
+
+        if let Some(strip) = &project.publish_strip_metadata {
+            features.require(Feature::publish_strip_metadata())?;
+            let metadata = project.metadata.as_ref().and_then(|m| m.as_table());
+            for key in strip {
+                let in_metadata = metadata.map_or(false, |m| m.contains_key(key));
+                let in_badges = me.badges.as_ref().map_or(false, |b| b.contains_key(key));
+                if !in_metadata && !in_badges {
+                    warnings.push(
+                        WarningCategory::Metadata,
+                        format!(
+                            "`package.publish-strip-metadata` entry `{}` does not match \
+                             any key in `[package.metadata]` or `[badges]`",
+                            key
+                        ),
+                    );
+                }
+            }
+        }
 
+        if project.standalone.is_some() {
+            features.require(Feature::standalone_package())?;
+        }
         let workspace_config = match (me.workspace.as_ref(), project.workspace.as_ref()) {
+            (Some(..), _) if project.standalone == Some(true) => bail!(
+                "cannot configure both `package.standalone` and \
+                 `[workspace]`, a standalone package cannot be a workspace root"
+            ),
+            (None, Some(..)) if project.standalone == Some(true) => bail!(
+                "cannot configure both `package.standalone` and \
+                 `package.workspace`, a standalone package cannot be a workspace member"
+            ),
+            (None, None) if project.standalone == Some(true) => WorkspaceConfig::Standalone,
             (Some(config), None) => WorkspaceConfig::Root(WorkspaceRootConfig::new(
                 package_root,
                 &config.members,
                 &config.default_members,
                 &config.exclude,
                 &config.metadata,
+                &config.limits,
             )),
             (None, root) => WorkspaceConfig::Member {
                 root: root.cloned(),
@@ -1310,22 +2747,42 @@ impl TomlManifest {
         };
         let profiles = me.profile.clone();
         if let Some(profiles) = &profiles {
-            profiles.validate(&features, &mut warnings)?;
+            let mut profile_warnings = vec![];
+            profiles.validate(&features, &mut profile_warnings)?;
+            warnings.extend(WarningCategory::Profile, profile_warnings);
         }
-        let publish = match project.publish {
-            Some(VecStringOrBool::VecString(ref vecstring)) => Some(vecstring.clone()),
-            Some(VecStringOrBool::Bool(false)) => Some(vec![]),
-            None | Some(VecStringOrBool::Bool(true)) => None,
-        };
+        if let Some(TomlPublish::Registry(registry)) = &project.publish {
+            warnings.push(
+                WarningCategory::Metadata,
+                format!(
+                    "`publish = \"{0}\"` is treated as `publish = [\"{0}\"]`; \
+                 write it as an array to silence this warning",
+                    registry
+                ),
+            );
+        }
+        let publish = project.publish.as_ref().and_then(TomlPublish::registries);
 
         if summary.features().contains_key("default-features") {
             warnings.push(
+                WarningCategory::Feature,
                 "`default-features = [\"..\"]` was found in [features]. \
                  Did you mean to use `default = [\"..\"]`?"
                     .to_string(),
             )
         }
 
+        if let Some(default) = me.features.as_ref().and_then(|f| f.get("default")) {
+            if default.is_empty() {
+                warnings.push(
+                    WarningCategory::Feature,
+                    "`default = []` was found in [features]. \
+                     An empty `default` feature has no effect and can be removed."
+                        .to_string(),
+                )
+            }
+        }
+
         if let Some(run) = &project.default_run {
             if !targets
                 .iter()
@@ -1358,22 +2815,26 @@ impl TomlManifest {
             project.im_a_teapot,
             project.default_run.clone(),
             Rc::clone(me),
-            project.metabuild.clone().map(|sov| sov.0),
+            project.metabuild.clone().map(Vec::from),
+            project.env.clone().unwrap_or_default(),
             resolve_behavior,
+            resolver_source,
         );
         if project.license_file.is_some() && project.license.is_some() {
-            manifest.warnings_mut().add_warning(
+            manifest.warnings_mut().push(
+                WarningCategory::Metadata,
                 "only one of `license` or \
                  `license-file` is necessary"
                     .to_string(),
             );
         }
-        for warning in warnings {
-            manifest.warnings_mut().add_warning(warning);
-        }
+        manifest.warnings_mut().append(warnings);
         for error in errors {
-            manifest.warnings_mut().add_critical_warning(error);
+            manifest
+                .warnings_mut()
+                .push_critical(WarningCategory::Target, error);
         }
+        manifest.deprecated_syntax_mut().insert(deprecated_syntax);
 
         manifest.feature_gate()?;
 
@@ -1425,6 +2886,9 @@ impl TomlManifest {
         if me.badges.is_some() {
             bail!("this virtual manifest specifies a [badges] section, which is not allowed");
         }
+        if me.lints.is_some() {
+            bail!("this virtual manifest specifies a [lints] section, which is not allowed");
+        }
 
         let mut nested_paths = Vec::new();
         let mut warnings = Vec::new();
@@ -1434,6 +2898,8 @@ impl TomlManifest {
         let features = Features::new(cargo_features, config, &mut warnings)?;
 
         let (replace, patch) = {
+            // Virtual manifests don't record deprecated syntax.
+            let mut deprecated_syntax = DeprecatedSyntax::default();
             let mut cx = Context {
                 pkgid: None,
                 deps: &mut deps,
@@ -1441,6 +2907,7 @@ impl TomlManifest {
                 nested_paths: &mut nested_paths,
                 config,
                 warnings: &mut warnings,
+                deprecated_syntax: &mut deprecated_syntax,
                 platform: None,
                 features: &features,
                 root,
@@ -1458,12 +2925,25 @@ impl TomlManifest {
         {
             features.require(Feature::resolver())?;
         }
+        if me
+            .workspace
+            .as_ref()
+            .map_or(false, |ws| ws.limits.is_some())
+        {
+            features.require(Feature::workspace_limits())?;
+        }
+        me.validate_lints(&features)?;
         let resolve_behavior = me
             .workspace
             .as_ref()
             .and_then(|ws| ws.resolver.as_deref())
             .map(|r| ResolveBehavior::from_manifest(r))
             .transpose()?;
+        let resolver_source = if resolve_behavior.is_some() {
+            ResolverSource::WorkspaceField
+        } else {
+            ResolverSource::Absent
+        };
         let workspace_config = match me.workspace {
             Some(ref config) => WorkspaceConfig::Root(WorkspaceRootConfig::new(
                 root,
@@ -1471,6 +2951,7 @@ impl TomlManifest {
                 &config.default_members,
                 &config.exclude,
                 &config.metadata,
+                &config.limits,
             )),
             None => {
                 bail!("virtual manifests must be configured with [workspace]");
@@ -1484,17 +2965,19 @@ impl TomlManifest {
                 profiles,
                 features,
                 resolve_behavior,
+                resolver_source,
             ),
             nested_paths,
         ))
     }
 
     fn replace(&self, cx: &mut Context<'_, '_>) -> CargoResult<Vec<(PackageIdSpec, Dependency)>> {
-        if self.patch.is_some() && self.replace.is_some() {
-            bail!("cannot specify both [replace] and [patch]");
+        if self.replace.is_some() {
+            cx.deprecated_syntax.insert(DeprecatedSyntax::REPLACE);
         }
         let mut replace = Vec::new();
         for (spec, replacement) in self.replace.iter().flatten() {
+            let key = vec!["replace".to_string(), spec.clone()];
             let mut spec = PackageIdSpec::parse(spec).chain_err(|| {
                 format!(
                     "replacements must specify a valid semver \
@@ -1514,7 +2997,9 @@ impl TomlManifest {
                 );
             }
 
-            let mut dep = replacement.to_dependency(spec.name().as_str(), cx, None)?;
+            let mut dep = replacement
+                .to_dependency(spec.name().as_str(), cx, None)
+                .map_err(|e| ManifestKeyError::new(e, key))?;
             {
                 let version = spec.version().ok_or_else(|| {
                     anyhow!(
@@ -1533,6 +3018,7 @@ impl TomlManifest {
     fn patch(&self, cx: &mut Context<'_, '_>) -> CargoResult<HashMap<Url, Vec<Dependency>>> {
         let mut patch = HashMap::new();
         for (url, deps) in self.patch.iter().flatten() {
+            let url_key = url.clone();
             let url = match &url[..] {
                 CRATES_IO_REGISTRY => CRATES_IO_INDEX.parse().unwrap(),
                 _ => cx
@@ -1546,13 +3032,57 @@ impl TomlManifest {
             patch.insert(
                 url,
                 deps.iter()
-                    .map(|(name, dep)| dep.to_dependency(name, cx, None))
+                    .map(|(name, dep)| {
+                        dep.to_dependency(name, cx, None).map_err(|e| {
+                            let key = vec!["patch".to_string(), url_key.clone(), name.clone()];
+                            ManifestKeyError::new(e, key).into()
+                        })
+                    })
                     .collect::<CargoResult<Vec<_>>>()?,
             );
         }
         Ok(patch)
     }
 
+    /// Feature gates and validates the `[lints]` and `[workspace.lints]`
+    /// tables.
+    fn validate_lints(&self, features: &Features) -> CargoResult<()> {
+        if let Some(lints) = &self.lints {
+            features.require(Feature::lints())?;
+            lints.validate("lints")?;
+        }
+        if let Some(lints) = self.workspace.as_ref().and_then(|ws| ws.lints.as_ref()) {
+            features.require(Feature::lints())?;
+            lints.validate("workspace.lints")?;
+        }
+        Ok(())
+    }
+
+    /// Parses every `[target.<platform>]` key up front, so a malformed
+    /// `cfg(..)` expression is reported before any dependency is processed.
+    fn validate_platform_targets(&self) -> CargoResult<Vec<(&str, Platform, &TomlPlatform)>> {
+        self.target
+            .iter()
+            .flatten()
+            .map(|(name, platform)| {
+                let cfg = name
+                    .parse::<Platform>()
+                    .chain_err(|| format!("invalid platform `{}` in `[target]` table", name))?;
+                // Entries under `build_dependencies` would be silently
+                // ignored.
+                if platform.build_dependencies.is_some() && platform.build_dependencies2.is_some() {
+                    bail!(
+                        "`[target.'{}']` sets both `build-dependencies` and \
+                         `build_dependencies`, only one can be specified; \
+                         merge the entries into `build-dependencies`",
+                        name
+                    );
+                }
+                Ok((name.as_str(), cfg, platform))
+            })
+            .collect()
+    }
+
     /// Returns the path to the build script if one exists for this crate.
     fn maybe_custom_build(
         &self,
@@ -1581,15 +3111,175 @@ impl TomlManifest {
         self.profile.is_some()
     }
 
+    /// Returns the named profile from `[profile]` with its `inherits` chain
+    /// merged in, see [`TomlProfiles::resolve`].
+    pub fn resolved_profile(&self, name: &str) -> CargoResult<Option<TomlProfile>> {
+        match &self.profile {
+            Some(profiles) => profiles.resolve(name),
+            None => Ok(None),
+        }
+    }
+
     pub fn features(&self) -> Option<&BTreeMap<InternedString, Vec<InternedString>>> {
         self.features.as_ref()
     }
+
+    /// The keys listed in `package.publish-strip-metadata`.
+    pub fn publish_strip_metadata(&self) -> &[String] {
+        self.package
+            .as_ref()
+            .or_else(|| self.project.as_ref())
+            .and_then(|project| project.publish_strip_metadata.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Returns the `[package.metadata]` and `[badges]` tables that
+    /// `package.publish-strip-metadata` removes from the published manifest,
+    /// as dotted paths like `package.metadata.internal`.
+    pub fn publish_stripped_metadata(&self) -> Vec<String> {
+        let project = match self.package.as_ref().or_else(|| self.project.as_ref()) {
+            Some(project) => project,
+            None => return Vec::new(),
+        };
+        let metadata = project.metadata.as_ref().and_then(|m| m.as_table());
+        let mut stripped = Vec::new();
+        for key in project.publish_strip_metadata.iter().flatten() {
+            if metadata.map_or(false, |m| m.contains_key(key)) {
+                stripped.push(format!("package.metadata.{}", key));
+            }
+            if self.badges.as_ref().map_or(false, |b| b.contains_key(key)) {
+                stripped.push(format!("badges.{}", key));
+            }
+        }
+        stripped
+    }
+
+    /// Returns whether this manifest passes the basic checks `cargo publish`
+    /// performs before packaging: it is a package, `publish` does not forbid
+    /// publishing, and every non-dev `path` or `git` dependency also has a
+    /// version.
+    ///
+    /// Dev-dependencies are ignored since ones without a version are
+    /// stripped when the package is published.
+    pub fn is_publishable(&self) -> bool {
+        let project = match self.package.as_ref().or_else(|| self.project.as_ref()) {
+            Some(project) => project,
+            None => return false,
+        };
+        let registries = project.publish.as_ref().and_then(TomlPublish::registries);
+        if registries.map_or(false, |r| r.is_empty()) {
+            return false;
+        }
+
+        fn unversioned(deps: &Option<BTreeMap<String, TomlDependency>>) -> bool {
+            deps.iter()
+                .flat_map(|deps| deps.values())
+                .any(|dep| match dep {
+                    TomlDependency::Detailed(d) => {
+                        (d.path.is_some() || d.git.is_some()) && d.version.is_none()
+                    }
+                    TomlDependency::Simple(_) => false,
+                })
+        }
+
+        !(unversioned(&self.dependencies)
+            || unversioned(&self.build_dependencies)
+            || unversioned(&self.build_dependencies2)
+            || self.target.iter().flat_map(|t| t.values()).any(|platform| {
+                unversioned(&platform.dependencies)
+                    || unversioned(&platform.build_dependencies)
+                    || unversioned(&platform.build_dependencies2)
+            }))
+    }
+}
+
+/// Converts a dependency table for the manifest generated by `cargo package`,
+/// keeping only the dependencies that `filter` accepts.
+///
+/// `filter` is called once per dependency, in name order, with the name and
+/// the dependency.
+fn map_deps(
+    config: &Config,
+    deps: Option<&BTreeMap<String, TomlDependency>>,
+    mut filter: impl FnMut(&str, &TomlDependency) -> bool,
+) -> CargoResult<Option<BTreeMap<String, TomlDependency>>> {
+    let deps = match deps {
+        Some(deps) => deps,
+        None => return Ok(None),
+    };
+    let deps = deps
+        .iter()
+        .filter(|(k, v)| filter(k, v))
+        .map(|(k, v)| Ok((k.clone(), map_dependency(config, k, v)?)))
+        .collect::<CargoResult<BTreeMap<_, _>>>()?;
+    Ok(Some(deps))
+}
+
+/// Converts one dependency for the manifest generated by `cargo package`.
+fn map_dependency(
+    config: &Config,
+    name: &str,
+    dep: &TomlDependency,
+) -> CargoResult<TomlDependency> {
+    match dep {
+        TomlDependency::Detailed(d) => {
+            let mut d = d.clone();
+            // Older versions of Cargo only understand a single requirement.
+            if let Some(version @ StringOrVecString::VecString(_)) = &d.version {
+                d.version = Some(version.to_requirement(name)?.into());
+            }
+            // A git dependency may name the registry version to use
+            // instead of its `version`.
+            if let Some(registry_version) = d.registry_version.take() {
+                if d.git.is_some() {
+                    d.version = Some(registry_version.into());
+                }
+            }
+            // Path dependencies become crates.io deps.
+            d.path.take();
+            // Same with git dependencies.
+            d.git.take();
+            d.branch.take();
+            d.tag.take();
+            d.rev.take();
+            // registry specifications are elaborated to the index URL
+            if let Some(registry) = d.registry.take() {
+                let src = SourceId::alt_registry(config, &registry)?;
+                d.registry_index = Some(src.url().to_string());
+            }
+            Ok(TomlDependency::Detailed(d))
+        }
+        TomlDependency::Simple(s) => Ok(TomlDependency::Detailed(DetailedTomlDependency {
+            version: Some(s.clone().into()),
+            ..Default::default()
+        })),
+    }
+}
+
+/// The key that the native library `links` is unique under when the package
+/// also sets `links-version`, like `openssl@1` or `foo@0.3`.
+///
+/// Versions that are semver compatible share a key, so only one of them can
+/// be linked, while incompatible versions of the library can be linked side
+/// by side.
+fn links_key(links: &str, version: &semver::Version) -> String {
+    if version.major > 0 {
+        format!("{}@{}", links, version.major)
+    } else if version.minor > 0 {
+        format!("{}@0.{}", links, version.minor)
+    } else {
+        format!("{}@0.0.{}", links, version.patch)
+    }
 }
 
 /// Returns the name of the README file for a `TomlProject`.
-fn readme_for_project(package_root: &Path, project: &TomlProject) -> Option<String> {
+fn readme_for_project(
+    package_root: &Path,
+    project: &TomlProject,
+    warnings: &mut Vec<String>,
+) -> Option<String> {
     match &project.readme {
-        None => default_readme_from_package_root(package_root),
+        None => default_readme_from_package_root(package_root, warnings),
         Some(value) => match value {
             StringOrBool::Bool(false) => None,
             StringOrBool::Bool(true) => Some("README.md".to_string()),
@@ -1600,15 +3290,39 @@ fn readme_for_project(package_root: &Path, project: &TomlProject) -> Option<Stri
 
 const DEFAULT_README_FILES: [&str; 3] = ["README.md", "README.txt", "README"];
 
+/// Lowercase spellings of `DEFAULT_README_FILES`, in the same order. These
+/// are only distinct files on case-sensitive file systems.
+const LOWERCASE_README_FILES: [&str; 3] = ["readme.md", "readme.txt", "readme"];
+
 /// Checks if a file with any of the default README file names exists in the package root.
 /// If so, returns a `String` representing that name.
-fn default_readme_from_package_root(package_root: &Path) -> Option<String> {
+///
+/// Lowercase names are also accepted, with a warning suggesting the
+/// conventional uppercase name.
+fn default_readme_from_package_root(
+    package_root: &Path,
+    warnings: &mut Vec<String>,
+) -> Option<String> {
     for &readme_filename in DEFAULT_README_FILES.iter() {
         if package_root.join(readme_filename).is_file() {
             return Some(readme_filename.to_string());
         }
     }
 
+    for (&lowercase, &conventional) in LOWERCASE_README_FILES
+        .iter()
+        .zip(DEFAULT_README_FILES.iter())
+    {
+        if package_root.join(lowercase).is_file() {
+            warnings.push(format!(
+                "using `{}` as the package README; consider renaming it to `{}` \
+                 so that it is found the same way on every file system",
+                lowercase, conventional
+            ));
+            return Some(lowercase.to_string());
+        }
+    }
+
     None
 }
 
@@ -1627,6 +3341,83 @@ fn unique_build_targets(targets: &[Target], package_root: &Path) -> Result<(), S
     Ok(())
 }
 
+/// Warns about package names that pass `validate_package_name` but are
+/// likely to cause trouble with tools, file systems or registries.
+///
+/// A trailing `.` is already rejected, since `.` is never valid in a name.
+fn warn_on_unusual_package_name(name: &str, warnings: &mut Vec<String>) {
+    if name.chars().any(char::is_uppercase) {
+        warnings.push(format!(
+            "package name `{}` contains uppercase characters; \
+             consider renaming it to `{}`, as package names are conventionally lowercase",
+            name,
+            name.to_lowercase()
+        ));
+    }
+    if name.chars().all(|ch| ch == '_' || ch == '-') {
+        warnings.push(format!(
+            "package name `{}` only consists of `_` and `-`, \
+             consider using a descriptive name",
+            name
+        ));
+    } else {
+        // A leading `-` is already rejected by `validate_package_name`.
+        if name.starts_with('_') {
+            warnings.push(format!(
+                "package name `{}` starts with `_`, which is easy to lose when the name \
+                 is copied or used as a file name",
+                name
+            ));
+        }
+        if let Some(last) = name.chars().last().filter(|&ch| ch == '_' || ch == '-') {
+            warnings.push(format!(
+                "package name `{}` ends with `{}`, which is easy to lose when the name \
+                 is copied or used as a file name",
+                name, last
+            ));
+        }
+    }
+    if name.chars().count() > 64 {
+        warnings.push(format!(
+            "package name `{}` is longer than 64 characters, \
+             which some registries do not accept",
+            name
+        ));
+    }
+}
+
+/// Returns the semver compatible part of the first comparator of a version
+/// requirement, up to its leftmost non-zero component like in `links_key`:
+/// `1` for `^1.2, <1.5`, `0.2` for `0.2.1` and `0.0.3` for `=0.0.3`.
+fn req_compat(req: &str) -> Option<String> {
+    let first = req.split(',').next()?;
+    let first = first.trim_start_matches(|c: char| "^~=<> ".contains(c));
+    let mut compat = Vec::new();
+    for part in first.trim().split('.') {
+        let part: u64 = part.parse().ok()?;
+        compat.push(part.to_string());
+        if part > 0 {
+            break;
+        }
+    }
+    Some(compat.join("."))
+}
+
+/// Returns the name of the table a dependency was declared in, like
+/// `[target.'cfg(unix)'.dev-dependencies]`.
+fn dependency_table_name(dep: &Dependency) -> String {
+    let kind = match dep.kind() {
+        DepKind::Normal => "dependencies",
+        DepKind::Development => "dev-dependencies",
+        DepKind::Build => "build-dependencies",
+    };
+    match dep.platform() {
+        Some(platform @ Platform::Cfg(_)) => format!("[target.'{}'.{}]", platform, kind),
+        Some(platform) => format!("[target.{}.{}]", platform, kind),
+        None => format!("[{}]", kind),
+    }
+}
+
 impl TomlDependency {
     fn to_dependency(
         &self,
@@ -1636,7 +3427,7 @@ impl TomlDependency {
     ) -> CargoResult<Dependency> {
         match *self {
             TomlDependency::Simple(ref version) => DetailedTomlDependency {
-                version: Some(version.clone()),
+                version: Some(version.clone().into()),
                 ..Default::default()
             }
             .to_dependency(name, cx, kind),
@@ -1670,7 +3461,7 @@ impl DetailedTomlDependency {
             cx.warnings.push(msg);
         }
 
-        if let Some(version) = &self.version {
+        for version in self.version.iter().flat_map(StringOrVecString::as_slice) {
             if version.contains('+') {
                 cx.warnings.push(format!(
                     "version requirement `{}` for dependency `{}` \
@@ -1700,6 +3491,38 @@ impl DetailedTomlDependency {
             }
         }
 
+        if let Some(registry_version) = &self.registry_version {
+            cx.features.require(Feature::registry_version())?;
+            if self.git.is_none() {
+                cx.warnings.push(format!(
+                    "key `registry-version` is ignored for dependency ({}), \
+                     it only applies to `git` dependencies",
+                    name_in_toml
+                ));
+            }
+            VersionReq::parse(registry_version).chain_err(|| {
+                format!(
+                    "failed to parse `registry-version` of dependency ({})",
+                    name_in_toml
+                )
+            })?;
+            let version = self.version.as_ref().and_then(|v| v.as_slice().first());
+            if let Some(version) = version {
+                let (git_compat, registry_compat) =
+                    (req_compat(version), req_compat(registry_version));
+                if git_compat.is_some()
+                    && registry_compat.is_some()
+                    && git_compat != registry_compat
+                {
+                    cx.warnings.push(format!(
+                        "dependency ({}) requires `{}` from git, but `{}` when published; \
+                         the versions are not semver compatible",
+                        name_in_toml, version, registry_version
+                    ));
+                }
+            }
+        }
+
         let new_source_id = match (
             self.git.as_ref(),
             self.path.as_ref(),
@@ -1772,9 +3595,7 @@ impl DetailedTomlDependency {
                 // always end up hashing to the same value no matter where it's
                 // built from.
                 if cx.source_id.is_path() {
-                    let path = cx.root.join(path);
-                    let path = util::normalize_path(&path);
-                    SourceId::for_path(&path)?
+                    SourceId::for_path(&resolve_path_dependency(cx.root, path))?
                 } else {
                     cx.source_id
                 }
@@ -1792,11 +3613,33 @@ impl DetailedTomlDependency {
             None => (name_in_toml, None),
         };
 
-        let version = self.version.as_deref();
+        if let Some(StringOrVecString::VecString(_)) = &self.version {
+            cx.features.require(Feature::version_list())?;
+        }
+        let version = match &self.version {
+            Some(version) => Some(version.to_requirement(name_in_toml)?),
+            None => None,
+        };
+        let version = version.as_deref();
         let mut dep = match cx.pkgid {
             Some(id) => Dependency::parse(pkg_name, version, new_source_id, id, cx.config)?,
             None => Dependency::parse_no_deprecated(pkg_name, version, new_source_id)?,
         };
+        if self.default_features2.is_some() {
+            cx.deprecated_syntax
+                .insert(DeprecatedSyntax::UNDERSCORE_KEYS);
+        }
+        if let (Some(kebab), Some(snake)) = (self.default_features, self.default_features2) {
+            if kebab != snake {
+                bail!(
+                    "dependency ({}) sets both `default-features = {}` and \
+                     `default_features = {}`, remove `default_features`",
+                    name_in_toml,
+                    kebab,
+                    snake
+                );
+            }
+        }
         dep.set_features(self.features.iter().flatten())
             .set_default_features(
                 self.default_features
@@ -1819,19 +3662,113 @@ impl DetailedTomlDependency {
             dep.set_kind(kind);
         }
         if let Some(name_in_toml) = explicit_name_in_toml {
-            cx.features.require(Feature::rename_dependency())?;
+            cx.features
+                .require(Feature::rename_dependency())
+                .chain_err(|| {
+                    format!(
+                        "dependency ({}) renames package `{}`, which is feature gated",
+                        name_in_toml, pkg_name
+                    )
+                })?;
             dep.set_explicit_name_in_toml(name_in_toml);
         }
 
         if let Some(p) = self.public {
-            cx.features.require(Feature::public_dependency())?;
+            cx.features
+                .require(Feature::public_dependency())
+                .chain_err(|| {
+                    format!(
+                        "dependency ({}) sets `public`, which is feature gated",
+                        name_in_toml
+                    )
+                })?;
 
-            if dep.kind() != DepKind::Normal {
-                bail!("'public' specifier can only be used on regular dependencies, not {:?} dependencies", dep.kind());
+            let kind = match dep.kind() {
+                DepKind::Normal => None,
+                DepKind::Development => Some("dev-dependency"),
+                DepKind::Build => Some("build-dependency"),
+            };
+            if let Some(kind) = kind {
+                bail!(
+                    "'public' can only be set on normal dependencies; `{}` is a {}",
+                    name_in_toml,
+                    kind
+                );
             }
 
             dep.set_public(p);
         }
+
+        match &self.artifact {
+            Some(artifact) => {
+                cx.features.require(Feature::artifact_dependencies())?;
+                let artifact = Artifact::parse(
+                    artifact.as_slice(),
+                    self.lib.unwrap_or(false),
+                    self.target.as_deref(),
+                )
+                .chain_err(|| format!("failed to parse artifact dependency `{}`", name_in_toml))?;
+                dep.set_artifact(artifact);
+            }
+            None => {
+                let artifact_only_keys = [
+                    (self.lib.is_some(), "lib"),
+                    (self.target.is_some(), "target"),
+                ];
+                for &(is_set, key_name) in &artifact_only_keys {
+                    if is_set {
+                        cx.warnings.push(format!(
+                            "key `{}` is ignored for dependency ({}) without `artifact`",
+                            key_name, name_in_toml
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(abi_version) = &self.minimum_abi_version {
+            cx.features.require(Feature::abi_version())?;
+            let source = match (&self.git, &self.path) {
+                (Some(_), _) => Some("git"),
+                (None, Some(_)) => Some("path"),
+                (None, None) => None,
+            };
+            if let Some(source) = source {
+                bail!(
+                    "dependency ({}) sets `minimum-abi-version`, but {} dependencies \
+                     have no ABI version; it can only be used with registry dependencies",
+                    name_in_toml,
+                    source
+                );
+            }
+            semver::Version::parse(abi_version).chain_err(|| {
+                format!(
+                    "failed to parse `minimum-abi-version` of dependency ({}), \
+                     expected a version like `1.2.0` rather than a requirement",
+                    name_in_toml
+                )
+            })?;
+        }
+
+        if let Some(checksum) = &self.checksum {
+            cx.features.require(Feature::dep_checksum())?;
+            if self.git.is_some() || self.path.is_some() {
+                bail!(
+                    "dependency ({}) sets `checksum`, which is only supported for \
+                     registry dependencies, not `git` or `path` dependencies",
+                    name_in_toml
+                );
+            }
+            if checksum.len() != 64 || !checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+                bail!(
+                    "invalid `checksum` for dependency ({}): `{}`\n\
+                     expected a SHA-256 checksum of 64 hexadecimal digits",
+                    name_in_toml,
+                    checksum
+                );
+            }
+            dep.set_checksum(Some(InternedString::new(&checksum.to_ascii_lowercase())));
+        }
         Ok(dep)
     }
 }
@@ -1860,6 +3797,7 @@ struct TomlTarget {
     harness: Option<bool>,
     #[serde(rename = "required-features")]
     required_features: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_edition")]
     edition: Option<String>,
 }
 
@@ -1933,3 +3871,129 @@ impl fmt::Debug for PathValue {
         self.0.fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{map_deps, TomlDependency, TomlProfile};
+    use super::{StringOrBool, StringOrVec, TomlOptLevel, U32OrBool, VecStringOrBool};
+    use crate::core::{Features, Shell};
+    use crate::util::Config;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn string_or_vec_iter_and_deref() {
+        let sov = StringOrVec::from(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(sov.len(), 2);
+        assert_eq!(sov.as_slice(), ["a", "b"]);
+        assert_eq!(&sov[1], "b");
+        assert!(sov.contains(&"a".to_string()));
+        assert_eq!((&sov).into_iter().collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(sov.clone().into_iter().collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(Vec::from(sov), ["a", "b"]);
+
+        #[derive(serde::Deserialize)]
+        struct Table {
+            v: StringOrVec,
+        }
+        let single: Table = toml::from_str("v = \"a\"").unwrap();
+        assert_eq!(single.v.iter().collect::<Vec<_>>(), ["a"]);
+    }
+
+    #[test]
+    fn display_as_toml() {
+        let opt = |level: &str, was_string| {
+            TomlOptLevel {
+                level: level.to_string(),
+                was_string,
+            }
+            .to_string()
+        };
+        assert_eq!(opt("2", false), "2");
+        assert_eq!(opt("2", true), "\"2\"");
+        assert_eq!(opt("s", true), "\"s\"");
+        assert_eq!(opt("z", true), "\"z\"");
+
+        assert_eq!(U32OrBool::U32(16).to_string(), "16");
+        assert_eq!(U32OrBool::Bool(false).to_string(), "false");
+
+        assert_eq!(
+            StringOrBool::String("unwind".to_string()).to_string(),
+            "\"unwind\""
+        );
+        assert_eq!(StringOrBool::Bool(true).to_string(), "true");
+
+        let list = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(
+            VecStringOrBool::VecString(list.clone()).to_string(),
+            "[\"a\", \"b\"]"
+        );
+        assert_eq!(VecStringOrBool::VecString(Vec::new()).to_string(), "[]");
+        assert_eq!(VecStringOrBool::Bool(false).to_string(), "false");
+
+        assert_eq!(StringOrVec::from(list).to_string(), "[\"a\", \"b\"]");
+        assert_eq!(
+            StringOrVec::from(vec!["a".to_string()]).to_string(),
+            "[\"a\"]"
+        );
+    }
+
+    #[test]
+    fn map_deps_stateful_filter() {
+        // Keep the user's cargo home and working directory out of the test.
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::new(
+            Shell::from_write(Box::new(Vec::new())),
+            dir.path().to_path_buf(),
+            dir.path().join(".cargo"),
+        );
+        let deps: BTreeMap<String, TomlDependency> = toml::from_str(
+            r#"
+            a = "1.0"
+            b = { version = "1.0", path = "b" }
+            c = "2.0"
+            "#,
+        )
+        .unwrap();
+
+        let mut seen = Vec::new();
+        let mapped = map_deps(&config, Some(&deps), |name, dep| {
+            seen.push(name.to_string());
+            name != "c" && dep.is_version_specified()
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(seen, ["a", "b", "c"]);
+        assert_eq!(mapped.keys().collect::<Vec<_>>(), ["a", "b"]);
+        match &mapped["b"] {
+            TomlDependency::Detailed(d) => assert!(d.path.is_none()),
+            TomlDependency::Simple(_) => panic!("expected a detailed dependency"),
+        }
+
+        assert!(map_deps(&config, None, |_, _| true).unwrap().is_none());
+    }
+
+    #[test]
+    fn codegen_units_range() {
+        let validate = |units| {
+            let profile = TomlProfile {
+                codegen_units: Some(units),
+                ..Default::default()
+            };
+            let mut warnings = Vec::new();
+            profile
+                .validate("release", &Features::default(), &mut warnings)
+                .map(|_| warnings)
+        };
+
+        let err = validate(0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`codegen-units` in `profile.release` must be at least 1, but is 0"
+        );
+        assert!(validate(1).unwrap().is_empty());
+        assert!(validate(256).unwrap().is_empty());
+        let warnings = validate(257).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("`codegen-units` in `profile.release` is 257;"));
+    }
+}
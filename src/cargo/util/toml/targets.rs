@@ -19,6 +19,7 @@ use super::{
     TomlLibTarget, TomlManifest, TomlTarget, TomlTestTarget,
 };
 use crate::core::compiler::CrateType;
+use crate::core::manifest::DeprecatedSyntax;
 use crate::core::{Edition, Feature, Features, Target};
 use crate::util::errors::{CargoResult, CargoResultExt};
 use crate::util::restricted_names;
@@ -32,6 +33,7 @@ pub fn targets(
     custom_build: &Option<StringOrBool>,
     metabuild: &Option<StringOrVec>,
     warnings: &mut Vec<String>,
+    deprecated_syntax: &mut DeprecatedSyntax,
     errors: &mut Vec<String>,
 ) -> CargoResult<Vec<Target>> {
     let mut targets = Vec::new();
@@ -45,6 +47,7 @@ pub fn targets(
         package_name,
         edition,
         warnings,
+        deprecated_syntax,
     )? {
         targets.push(target);
         has_lib = true;
@@ -66,6 +69,7 @@ pub fn targets(
         edition,
         package.autobins,
         warnings,
+        deprecated_syntax,
         errors,
         has_lib,
     )?);
@@ -77,6 +81,7 @@ pub fn targets(
         edition,
         package.autoexamples,
         warnings,
+        deprecated_syntax,
         errors,
     )?);
 
@@ -87,6 +92,7 @@ pub fn targets(
         edition,
         package.autotests,
         warnings,
+        deprecated_syntax,
         errors,
     )?);
 
@@ -97,6 +103,7 @@ pub fn targets(
         edition,
         package.autobenches,
         warnings,
+        deprecated_syntax,
         errors,
     )?);
 
@@ -121,7 +128,7 @@ pub fn targets(
     if let Some(metabuild) = metabuild {
         // Verify names match available build deps.
         let bdeps = manifest.build_dependencies.as_ref();
-        for name in &metabuild.0 {
+        for name in metabuild {
             if !bdeps.map_or(false, |bd| bd.contains_key(name)) {
                 anyhow::bail!(
                     "metabuild package `{}` must be specified in `build-dependencies`",
@@ -146,6 +153,7 @@ fn clean_lib(
     package_name: &str,
     edition: Edition,
     warnings: &mut Vec<String>,
+    deprecated_syntax: &mut DeprecatedSyntax,
 ) -> CargoResult<Option<Target>> {
     let inferred = inferred_lib(package_root);
     let lib = match toml_lib {
@@ -207,6 +215,9 @@ fn clean_lib(
     //
     // A plugin requires exporting plugin_registrar so a crate cannot be
     // both at once.
+    if lib.plugin == Some(true) {
+        deprecated_syntax.insert(DeprecatedSyntax::PLUGIN);
+    }
     let crate_types = match (lib.crate_types(), lib.plugin, lib.proc_macro()) {
         (Some(kinds), _, _) if kinds.contains(&"proc-macro".to_string()) => {
             if let Some(true) = lib.plugin {
@@ -235,7 +246,7 @@ fn clean_lib(
     };
 
     let mut target = Target::lib_target(&lib.name(), crate_types, path, edition);
-    configure(features, lib, &mut target)?;
+    configure(features, lib, &mut target, deprecated_syntax)?;
     Ok(Some(target))
 }
 
@@ -247,6 +258,7 @@ fn clean_bins(
     edition: Edition,
     autodiscover: Option<bool>,
     warnings: &mut Vec<String>,
+    deprecated_syntax: &mut DeprecatedSyntax,
     errors: &mut Vec<String>,
     has_lib: bool,
 ) -> CargoResult<Vec<Target>> {
@@ -321,7 +333,7 @@ fn clean_bins(
 
         let mut target =
             Target::bin_target(&bin.name(), path, bin.required_features.clone(), edition);
-        configure(features, bin, &mut target)?;
+        configure(features, bin, &mut target, deprecated_syntax)?;
         result.push(target);
     }
     return Ok(result);
@@ -353,6 +365,7 @@ fn clean_examples(
     edition: Edition,
     autodiscover: Option<bool>,
     warnings: &mut Vec<String>,
+    deprecated_syntax: &mut DeprecatedSyntax,
     errors: &mut Vec<String>,
 ) -> CargoResult<Vec<Target>> {
     let inferred = infer_from_directory(&package_root.join("examples"));
@@ -384,7 +397,7 @@ fn clean_examples(
             toml.required_features.clone(),
             edition,
         );
-        configure(features, &toml, &mut target)?;
+        configure(features, &toml, &mut target, deprecated_syntax)?;
         result.push(target);
     }
 
@@ -398,6 +411,7 @@ fn clean_tests(
     edition: Edition,
     autodiscover: Option<bool>,
     warnings: &mut Vec<String>,
+    deprecated_syntax: &mut DeprecatedSyntax,
     errors: &mut Vec<String>,
 ) -> CargoResult<Vec<Target>> {
     let inferred = infer_from_directory(&package_root.join("tests"));
@@ -419,7 +433,7 @@ fn clean_tests(
     for (path, toml) in targets {
         let mut target =
             Target::test_target(&toml.name(), path, toml.required_features.clone(), edition);
-        configure(features, &toml, &mut target)?;
+        configure(features, &toml, &mut target, deprecated_syntax)?;
         result.push(target);
     }
     Ok(result)
@@ -432,6 +446,7 @@ fn clean_benches(
     edition: Edition,
     autodiscover: Option<bool>,
     warnings: &mut Vec<String>,
+    deprecated_syntax: &mut DeprecatedSyntax,
     errors: &mut Vec<String>,
 ) -> CargoResult<Vec<Target>> {
     let mut legacy_warnings = vec![];
@@ -474,7 +489,7 @@ fn clean_benches(
     for (path, toml) in targets {
         let mut target =
             Target::bench_target(&toml.name(), path, toml.required_features.clone(), edition);
-        configure(features, &toml, &mut target)?;
+        configure(features, &toml, &mut target, deprecated_syntax)?;
         result.push(target);
     }
 
@@ -770,7 +785,15 @@ fn validate_unique_names(targets: &[TomlTarget], target_kind: &str) -> CargoResu
     Ok(())
 }
 
-fn configure(features: &Features, toml: &TomlTarget, target: &mut Target) -> CargoResult<()> {
+fn configure(
+    features: &Features,
+    toml: &TomlTarget,
+    target: &mut Target,
+    deprecated_syntax: &mut DeprecatedSyntax,
+) -> CargoResult<()> {
+    if toml.crate_type2.is_some() || toml.proc_macro_raw2.is_some() {
+        deprecated_syntax.insert(DeprecatedSyntax::UNDERSCORE_KEYS);
+    }
     let t2 = target.clone();
     target
         .set_tested(toml.test.unwrap_or_else(|| t2.tested()))
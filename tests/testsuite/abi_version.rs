@@ -0,0 +1,141 @@
+//! Tests for `minimum-abi-version` on dependencies, the `abi-version`
+//! unstable feature.
+
+use super::config::ConfigBuilder;
+use cargo::core::Workspace;
+use cargo_test_support::{basic_manifest, project};
+
+#[cargo_test]
+fn feature_gated() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = { version = "1.4", minimum-abi-version = "1.2.0" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  feature `abi-version` is required
+
+  consider adding `cargo-features = [\"abi-version\"]` to the manifest
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn kept_in_published_manifest() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["abi-version"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = { version = "1.4", minimum-abi-version = "1.2.0" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    let config = ConfigBuilder::new().nightly_features_allowed(true).build();
+    let ws = Workspace::new(&p.root().join("Cargo.toml"), &config).unwrap();
+    let toml = ws.current().unwrap().to_registry_toml(&ws).unwrap();
+    assert!(
+        toml.contains("[dependencies.bar]\nversion = \"1.4\"\nminimum-abi-version = \"1.2.0\"\n"),
+        "{}",
+        toml
+    );
+}
+
+#[cargo_test]
+fn requirement_is_rejected() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["abi-version"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = { version = "1.4", minimum-abi-version = "^1.2" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  failed to parse `minimum-abi-version` of dependency (bar), \
+expected a version like `1.2.0` rather than a requirement
+
+Caused by:
+  [..]
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn path_dependency_is_rejected() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["abi-version"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = { path = "bar", minimum-abi-version = "1.2.0" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "1.4.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  dependency (bar) sets `minimum-abi-version`, but path dependencies \
+have no ABI version; it can only be used with registry dependencies
+",
+        )
+        .run();
+}
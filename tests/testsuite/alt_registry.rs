@@ -639,7 +639,7 @@ fn bad_registry_name() {
         .with_status(101)
         .with_stderr(
             "\
-[ERROR] failed to parse manifest at `[CWD]/Cargo.toml`
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml:7:17`
 
 Caused by:
   invalid character ` ` in registry name: `bad name`, [..]",
@@ -804,6 +804,7 @@ fn alt_reg_metadata() {
                                 "kind": null,
                                 "rename": null,
                                 "optional": false,
+                                "public": false,
                                 "uses_default_features": true,
                                 "features": [],
                                 "target": null,
@@ -816,6 +817,7 @@ fn alt_reg_metadata() {
                                 "kind": null,
                                 "rename": null,
                                 "optional": false,
+                                "public": false,
                                 "uses_default_features": true,
                                 "features": [],
                                 "target": null,
@@ -872,6 +874,7 @@ fn alt_reg_metadata() {
                                 "kind": null,
                                 "rename": null,
                                 "optional": false,
+                                "public": false,
                                 "uses_default_features": true,
                                 "features": [],
                                 "target": null,
@@ -957,6 +960,7 @@ fn alt_reg_metadata() {
                                 "kind": null,
                                 "rename": null,
                                 "optional": false,
+                                "public": false,
                                 "uses_default_features": true,
                                 "features": [],
                                 "target": null,
@@ -969,6 +973,7 @@ fn alt_reg_metadata() {
                                 "kind": null,
                                 "rename": null,
                                 "optional": false,
+                                "public": false,
                                 "uses_default_features": true,
                                 "features": [],
                                 "target": null,
@@ -1006,6 +1011,7 @@ fn alt_reg_metadata() {
                                 "kind": null,
                                 "rename": null,
                                 "optional": false,
+                                "public": false,
                                 "uses_default_features": true,
                                 "features": [],
                                 "target": null,
@@ -1102,6 +1108,7 @@ fn unknown_registry() {
                       "kind": null,
                       "rename": null,
                       "optional": false,
+                      "public": false,
                       "uses_default_features": true,
                       "features": [],
                       "target": null,
@@ -1163,6 +1170,7 @@ fn unknown_registry() {
                       "kind": null,
                       "rename": null,
                       "optional": false,
+                      "public": false,
                       "uses_default_features": true,
                       "features": [],
                       "target": null,
@@ -1182,7 +1190,8 @@ fn unknown_registry() {
                   "homepage": null,
                   "documentation": null,
                   "edition": "2015",
-                  "links": null
+                  "links": null,
+                  "deprecated_syntax": ["project-table"]
                 }
               ],
               "workspace_members": [
@@ -1287,7 +1296,7 @@ fn registries_index_relative_path_not_allowed() {
     p.cargo("build")
         .with_stderr(&format!(
             "\
-error: failed to parse manifest at `{root}/foo/Cargo.toml`
+error: failed to parse manifest at `{root}/foo/Cargo.toml:7:17`
 
 Caused by:
   invalid index URL for registry `relative` defined in [..]/.cargo/config
@@ -0,0 +1,199 @@
+//! Tests for artifact dependencies, the `artifact-dependencies` unstable
+//! feature.
+
+use cargo_test_support::{basic_bin_manifest, basic_manifest, project};
+
+#[cargo_test]
+fn feature_gated() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = { path = "bar", artifact = "bin" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_bin_manifest("bar"))
+        .file("bar/src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  feature `artifact-dependencies` is required
+
+  consider adding `cargo-features = [\"artifact-dependencies\"]` to the manifest
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn artifact_dependency_resolves() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["artifact-dependencies"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [build-dependencies]
+                bar = { path = "bar", artifact = ["bin", "cdylib"], lib = false }
+                baz = { path = "baz", artifact = "staticlib", lib = true, target = "wasm32-unknown-unknown" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("build.rs", "fn main() {}")
+        .file("bar/Cargo.toml", &basic_bin_manifest("bar"))
+        .file("bar/src/main.rs", "fn main() {}")
+        .file("baz/Cargo.toml", &basic_manifest("baz", "0.0.1"))
+        .file("baz/src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile")
+        .masquerade_as_nightly_cargo()
+        .run();
+    let lock = p.read_lockfile();
+    assert!(lock.contains("name = \"bar\""));
+    assert!(lock.contains("name = \"baz\""));
+}
+
+#[cargo_test]
+fn invalid_artifact() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["artifact-dependencies"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = { path = "bar", artifact = "rlib" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.0.1"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  failed to parse artifact dependency `bar`
+
+Caused by:
+  `rlib` is not a valid artifact kind, expected one of `bin`, `cdylib` or `staticlib`
+",
+        )
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            cargo-features = ["artifact-dependencies"]
+
+            [package]
+            name = "foo"
+            version = "0.0.1"
+
+            [dependencies]
+            bar = { path = "bar", artifact = [] }
+        "#,
+    );
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  failed to parse artifact dependency `bar`
+
+Caused by:
+  `artifact` must list at least one artifact kind
+",
+        )
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            cargo-features = ["artifact-dependencies"]
+
+            [package]
+            name = "foo"
+            version = "0.0.1"
+
+            [dependencies]
+            bar = { path = "bar", artifact = "bin", lib = true }
+        "#,
+    );
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  failed to parse artifact dependency `bar`
+
+Caused by:
+  `lib = true` cannot be combined with the `bin` artifact kind
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn lib_without_artifact() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = { path = "bar", lib = true }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.0.1"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .with_stderr(
+            "\
+[WARNING] key `lib` is ignored for dependency (bar) without `artifact`
+[CHECKING] bar v0.0.1 ([..])
+[CHECKING] foo v0.0.1 ([..])
+[FINISHED] [..]
+",
+        )
+        .run();
+}
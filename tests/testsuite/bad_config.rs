@@ -193,7 +193,7 @@ Caused by:
   could not parse TOML configuration in `[..]`
 
 Caused by:
-  could not parse input as TOML
+  could not parse input as TOML at `[..]`
 
 Caused by:
   expected an equals, found eof at line 1 column 2
@@ -439,7 +439,7 @@ fn malformed_override() {
 [ERROR] failed to parse manifest at `[..]`
 
 Caused by:
-  could not parse input as TOML
+  could not parse input as TOML at `[..]`
 
 Caused by:
   expected a table key, found a newline at line 8 column 27
@@ -593,6 +593,9 @@ fn duplicate_deps() {
 Caused by:
   Dependency 'bar' has different source paths depending on the build target. Each dependency must \
 have a single canonical source path irrespective of build target.
+
+  `bar` in [dependencies] uses [..]/shim-bar
+  `bar` in [target.x86_64-unknown-linux-gnu.dependencies] uses [..]/linux-bar
 ",
         )
         .run();
@@ -632,6 +635,130 @@ fn duplicate_deps_diff_sources() {
 Caused by:
   Dependency 'bar' has different source paths depending on the build target. Each dependency must \
 have a single canonical source path irrespective of build target.
+
+  `bar` in [target.i686-unknown-linux-gnu.dependencies] uses [..]/shim-bar
+  `bar` in [target.x86_64-unknown-linux-gnu.dependencies] uses [..]/linux-bar
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn duplicate_deps_diff_kinds() {
+    Package::new("bar", "0.0.1").publish();
+    let p = project()
+        .file("shim-bar/Cargo.toml", &basic_manifest("bar", "0.0.1"))
+        .file("shim-bar/src/lib.rs", "pub fn a() {}")
+        .file(
+            "Cargo.toml",
+            r#"
+               [package]
+               name = "qqq"
+               version = "0.0.1"
+               authors = []
+
+               [dependencies]
+               bar = { path = "shim-bar" }
+
+               [target.'cfg(unix)'.build-dependencies]
+               bar = "0.0.1"
+            "#,
+        )
+        .file("src/main.rs", r#"fn main () {}"#)
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  Dependency 'bar' has different source paths depending on the build target. Each dependency must \
+have a single canonical source path irrespective of build target.
+
+  `bar` in [dependencies] uses [..]/shim-bar
+  `bar` in [target.'cfg(unix)'.build-dependencies] uses registry `[..]`
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn unusual_package_names() {
+    let p = project().file("src/lib.rs", "").build();
+    let long = "a".repeat(65);
+    let cases = [
+        (
+            "foo-",
+            "package name `foo-` ends with `-`, which is easy to lose when the name \
+             is copied or used as a file name"
+                .to_string(),
+        ),
+        (
+            &long[..],
+            format!(
+                "package name `{}` is longer than 64 characters, \
+                 which some registries do not accept",
+                long
+            ),
+        ),
+        (
+            "__",
+            "package name `__` only consists of `_` and `-`, \
+             consider using a descriptive name"
+                .to_string(),
+        ),
+        (
+            "Foo_Bar",
+            "package name `Foo_Bar` contains uppercase characters; \
+             consider renaming it to `foo_bar`, as package names are conventionally lowercase"
+                .to_string(),
+        ),
+        (
+            "_foo",
+            "package name `_foo` starts with `_`, which is easy to lose when the name \
+             is copied or used as a file name"
+                .to_string(),
+        ),
+        (
+            "foo_",
+            "package name `foo_` ends with `_`, which is easy to lose when the name \
+             is copied or used as a file name"
+                .to_string(),
+        ),
+    ];
+    for (name, warning) in cases.iter() {
+        p.change_file("Cargo.toml", &basic_manifest(name, "0.0.1"));
+        p.cargo("check")
+            .with_stderr_contains(format!("[WARNING] {}", warning))
+            .run();
+    }
+
+    p.change_file("Cargo.toml", &basic_manifest("-foo", "0.0.1"));
+    p.cargo("check")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  invalid character `-` in package name: `-foo`, the first character must be a Unicode XID \
+start character (most letters or `_`)
+",
+        )
+        .run();
+
+    p.change_file("Cargo.toml", &basic_manifest("foo.", "0.0.1"));
+    p.cargo("check")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  invalid character `.` in package name: `foo.`, characters must be Unicode XID characters \
+(numbers, `-`, `_`, or most letters)
 ",
         )
         .run();
@@ -715,7 +842,7 @@ warning: use `[profile.dev]` to configure debug builds
     p.cargo("build")
         .with_stderr(
             "\
-warning: unused manifest key: project.bulid
+warning: unused manifest key: project.bulid (did you mean `build`?)
 [COMPILING] foo [..]
 [FINISHED] dev [unoptimized + debuginfo] target(s) in [..]
 ",
@@ -750,6 +877,77 @@ warning: unused manifest key: lib.build
         .run();
 }
 
+#[cargo_test]
+fn unused_keys_nested() {
+    // Unknown keys inside dependency and profile tables are reported with
+    // their full dotted path.
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [dependencies]
+                bar = { path = "bar", optionl = true }
+
+                [dependencies.baz]
+                path = "baz"
+                default-featuers = false
+
+                [profile.release]
+                opt-levle = 3
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .file("baz/Cargo.toml", &basic_manifest("baz", "0.1.0"))
+        .file("baz/src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .with_stderr_contains(
+            "\
+warning: unused manifest key: dependencies.bar.optionl (did you mean `optional`?)
+warning: unused manifest key: dependencies.baz.default-featuers (did you mean `default-features`?)
+warning: unused manifest key: profile.release.opt-levle (did you mean `opt-level`?)
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn unused_keys_suggest_known_key() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                rust-verison = "1.50"
+
+                [dependencis]
+                bar = "1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .with_stderr(
+            "\
+warning: unused manifest key: dependencis (did you mean `dependencies`?)
+warning: unused manifest key: package.rust-verison (did you mean `rust-version`?)
+[CHECKING] foo v0.1.0 ([CWD])
+[FINISHED] [..]
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn unused_keys_in_virtual_manifest() {
     let p = project()
@@ -829,6 +1027,200 @@ in the future.
         .run();
 }
 
+#[cargo_test]
+fn duplicate_table_historically_allowed_is_warned() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+authors = []
+
+[dependencies]
+
+[dependencies.bar]
+path = "bar"
+
+[dependencies]
+"#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.0.1"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_stderr_contains(
+            "\
+warning: TOML file found which contains invalid syntax and will soon not parse
+at `Cargo.toml`.
+
+The TOML spec requires that each table header is defined at most once, but
+historical versions of Cargo have erroneously accepted this file. The table
+definitions will need to be merged together with one table header to proceed,
+and this will become a hard error in the future.
+
+duplicate table header `[dependencies]` found at line 12 (first defined at line 7),
+move the keys below line 12 into the first `[dependencies]` table and remove the
+second header.
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn invalid_toml_historically_allowed_in_dependency_is_warned() {
+    Package::new("bar", "0.1.0")
+        .file(
+            "Cargo.toml",
+            r#"
+[package] name = "bar"
+version = "0.1.0"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = "0.1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_stderr_contains(
+            "\
+warning: TOML file found which contains invalid syntax and will soon not parse
+at `[..]bar-0.1.0/Cargo.toml`.
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn historically_allowed_toml_is_error_when_strict() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+authors = []
+
+[dependencies] bar = { path = "bar" }
+"#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.0.1"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build -Zstrict-toml")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  could not parse input as TOML at `[..]`
+
+Caused by:
+  expected newline, [..] at line 7 column [..]
+",
+        )
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "foo"
+version = "0.0.1"
+authors = []
+
+[dependencies]
+
+[dependencies.bar]
+path = "bar"
+
+[dependencies]
+"#,
+    );
+
+    p.cargo("build -Zstrict-toml")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  could not parse input as TOML at `[..]`
+
+Caused by:
+  redefinition of table `dependencies`[..] at line 12 column 1
+",
+        )
+        .run();
+
+    // Without the flag it is only a warning.
+    p.cargo("build")
+        .with_stderr_contains("warning: TOML file found which contains invalid syntax [..]")
+        .run();
+}
+
+#[cargo_test]
+fn historically_allowed_toml_in_dependency_is_error_when_strict() {
+    Package::new("bar", "0.1.0")
+        .file(
+            "Cargo.toml",
+            r#"
+[package] name = "bar"
+version = "0.1.0"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = "0.1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    // The flag covers every manifest that is parsed, not only the members.
+    p.cargo("build -Zstrict-toml")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr_contains(
+            "\
+Caused by:
+  could not parse input as TOML at `[..]bar-0.1.0/Cargo.toml:[..]`
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn ambiguous_git_reference() {
     let p = project()
@@ -862,6 +1254,161 @@ Caused by:
         .run();
 }
 
+#[cargo_test]
+fn dependency_error_position() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.0"
+                authors = []
+
+                [dependencies]
+                baz = "1.0"
+
+                [target.'cfg(unix)'.dependencies]
+                bar = { git = "http://127.0.0.1", branch = "master", tag = "some-tag" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml:11:17`
+
+Caused by:
+  dependency (bar) specification is ambiguous. Only one of `branch`, `tag` or `rev` is allowed.
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn dependency_error_position_is_exact() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.0"
+                description = """
+                bar-baz = { git = "http://127.0.0.1" }
+                """
+
+                [dependencies]
+                bar_baz = "1.0"
+                bar-baz = { git = "http://127.0.0.1", branch = "master", tag = "some-tag" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml:11:17`
+
+Caused by:
+  dependency (bar-baz) specification is ambiguous. Only one of `branch`, `tag` or `rev` is allowed.
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn conflicting_default_features_alias() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.0"
+                authors = []
+
+                [dependencies.bar]
+                path = "bar"
+                default-features = false
+                default_features = true
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.0.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  dependency (bar) sets both `default-features = false` and `default_features = true`, \
+remove `default_features`
+",
+        )
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            version = "0.0.0"
+            authors = []
+
+            [dependencies.bar]
+            path = "bar"
+            default-features = false
+            default_features = false
+        "#,
+    );
+    p.cargo("build").run();
+}
+
+#[cargo_test]
+fn duplicate_target_build_dependencies_alias() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.0"
+                authors = []
+
+                [target.'cfg(unix)'.build-dependencies]
+                bar = { path = "bar" }
+
+                [target.'cfg(unix)'.build_dependencies]
+                baz = { path = "baz" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  `[target.'cfg(unix)']` sets both `build-dependencies` and `build_dependencies`, \
+only one can be specified; merge the entries into `build-dependencies`
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn fragment_in_git_url() {
     let p = project()
@@ -1366,6 +1913,106 @@ fn warn_semver_metadata() {
         .run();
 }
 
+#[cargo_test]
+fn dependency_version_list() {
+    Package::new("bar", "1.0.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "foo"
+            version = "1.0.0"
+
+            [dependencies]
+            bar = { version = ["1.0", "1.0"] }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    p.cargo("generate-lockfile")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+error: failed to parse manifest at `[..]`
+
+Caused by:
+  feature `version-list` is required
+
+  consider adding `cargo-features = [\"version-list\"]` to the manifest
+",
+        )
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+        cargo-features = ["version-list"]
+
+        [package]
+        name = "foo"
+        version = "1.0.0"
+        license = "MIT"
+        description = "foo"
+
+        [dependencies]
+        bar = { version = ["1.0", "1.0"] }
+        "#,
+    );
+    p.cargo("generate-lockfile")
+        .masquerade_as_nightly_cargo()
+        .run();
+    assert!(p.read_lockfile().contains("name = \"bar\""));
+
+    // The published manifest has a plain string requirement.
+    p.cargo("package --no-verify")
+        .masquerade_as_nightly_cargo()
+        .run();
+    let f = std::fs::File::open(p.root().join("target/package/foo-1.0.0.crate")).unwrap();
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(f));
+    let mut published = String::new();
+    for ent in archive.entries().unwrap() {
+        let mut ent = ent.unwrap();
+        if ent.path().unwrap().ends_with("foo-1.0.0/Cargo.toml") {
+            std::io::Read::read_to_string(&mut ent, &mut published).unwrap();
+        }
+    }
+    assert!(
+        published.contains("[dependencies.bar]\nversion = \"1.0\""),
+        "{}",
+        published
+    );
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+        cargo-features = ["version-list"]
+
+        [package]
+        name = "foo"
+        version = "1.0.0"
+
+        [dependencies]
+        bar = { version = ["1.28", "1.35"] }
+        "#,
+    );
+    p.cargo("generate-lockfile")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+error: failed to parse manifest at `[..]`
+
+Caused by:
+  dependency (bar) lists alternative version requirements `1.28`, `1.35`, \
+but alternatives cannot be combined into a single requirement; \
+use one requirement that covers all of them instead
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn bad_target_cfg() {
     // Invalid type in a StringList.
@@ -1499,3 +2146,38 @@ note: Sources are not allowed to be defined multiple times.
         )
         .run();
 }
+
+#[cargo_test]
+fn dependency_names_differ_only_by_case() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "foo"
+            version = "0.1.0"
+
+            [dependencies]
+            bar = { path = "bar" }
+
+            [dev-dependencies]
+            Bar = { path = "baz", package = "baz" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .file("baz/Cargo.toml", &basic_manifest("baz", "0.1.0"))
+        .file("baz/src/lib.rs", "")
+        .build();
+
+    p.cargo("fetch")
+        .with_stderr(
+            "\
+[WARNING] dependencies `Bar` in [dev-dependencies] and `bar` in [dependencies] \
+differ only by case; registries treat package names case-insensitively, \
+so they may refer to the same package
+",
+        )
+        .run();
+}
@@ -194,7 +194,7 @@ fn cargo_compile_with_invalid_manifest2() {
 [ERROR] failed to parse manifest at `[..]`
 
 Caused by:
-  could not parse input as TOML
+  could not parse input as TOML at `[..]Cargo.toml:3:23`
 
 Caused by:
   invalid TOML value, did you mean to use a quoted string? at line 3 column 23
@@ -214,7 +214,7 @@ fn cargo_compile_with_invalid_manifest3() {
 [ERROR] failed to parse manifest at `[..]`
 
 Caused by:
-  could not parse input as TOML
+  could not parse input as TOML at `[..]`
 
 Caused by:
   invalid TOML value, did you mean to use a quoted string? at line 1 column 5
@@ -538,7 +538,7 @@ fn cargo_compile_with_invalid_non_numeric_dep_version() {
         .with_status(101)
         .with_stderr(
             "\
-[ERROR] failed to parse manifest at `[CWD]/Cargo.toml`
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml:7:17`
 
 Caused by:
   failed to parse the version requirement `y` for dependency `crossbeam`
@@ -2390,7 +2390,7 @@ Caused by:
   could not parse TOML configuration in `[..]`
 
 Caused by:
-  could not parse input as TOML
+  could not parse input as TOML at `[..]`
 
 Caused by:
   expected an equals, found an identifier at line 1 column 6
@@ -4371,7 +4371,13 @@ fn target_edition_override() {
         )
         .build();
 
-    p.cargo("build -v").run();
+    p.cargo("build -v")
+        .with_stderr_contains(
+            "[WARNING] lib target uses edition 2015, but the package uses edition 2018; \
+             mixing editions within a package is easy to miss, so make sure \
+             this is intentional and documented",
+        )
+        .run();
 }
 
 #[cargo_test]
@@ -196,6 +196,79 @@ fn cargo_build_plan_build_script() {
         .run();
 }
 
+#[cargo_test]
+fn build_plan_declared_env() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["package-env"]
+
+                [package]
+                name = "foo"
+                version = "0.5.0"
+
+                [package.env]
+                FOO_TOKEN = { required = true, description = "token for the foo service" }
+                FOO_OPTIONAL = {}
+            "#,
+        )
+        .file("src/main.rs", r#"fn main() {}"#)
+        .file("build.rs", r#"fn main() {}"#)
+        .build();
+
+    p.cargo("build --build-plan -Zunstable-options")
+        .env("FOO_TOKEN", "hunter2")
+        .masquerade_as_nightly_cargo()
+        .with_stdout_contains(r#"[..]"FOO_TOKEN":"hunter2"[..]"#)
+        .run();
+
+    p.cargo("build --build-plan -Zunstable-options")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] environment variable `FOO_TOKEN` is required by the build script of \
+`foo v0.5.0 ([..])` (token for the foo service)
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn build_plan_declared_env_without_build_script() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["package-env"]
+
+                [package]
+                name = "foo"
+                version = "0.5.0"
+
+                [package.env]
+                FOO_TOKEN = {}
+            "#,
+        )
+        .file("src/main.rs", r#"fn main() {}"#)
+        .build();
+
+    p.cargo("build --build-plan -Zunstable-options")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  package `foo v0.5.0 ([..])` declares environment variables in `package.env` \
+but does not have a custom build script
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn build_plan_with_dev_dep() {
     Package::new("bar", "0.1.0").publish();
@@ -0,0 +1,161 @@
+//! Tests for the `cargo manifest` command.
+
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+
+#[cargo_test]
+fn sort_deps_requires_unstable_options() {
+    let p = project().file("src/lib.rs", "").build();
+
+    p.cargo("manifest sort-deps")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] the `cargo manifest` command is unstable, pass `-Z unstable-options` to enable it
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn sort_deps() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+
+[dependencies]
+# serialization
+serde = "1.0"
+bar = { path = "bar" } # local
+anyhow = "1.0"
+
+[target.'cfg(unix)'.build-dependencies]
+libc = "0.2"
+cc = "1.0"
+
+[dev-dependencies.zed]
+version = "0.1"
+
+[dev-dependencies.abc]
+version = "0.1"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest sort-deps -Zunstable-options")
+        .masquerade_as_nightly_cargo()
+        .with_stderr("[SORTED] [..]Cargo.toml")
+        .run();
+
+    assert_eq!(
+        p.read_file("Cargo.toml"),
+        r#"
+[package]
+name = "foo"
+version = "0.1.0"
+
+[dependencies]
+anyhow = "1.0"
+bar = { path = "bar" } # local
+# serialization
+serde = "1.0"
+
+[target.'cfg(unix)'.build-dependencies]
+cc = "1.0"
+libc = "0.2"
+
+[dev-dependencies.zed]
+version = "0.1"
+
+[dev-dependencies.abc]
+version = "0.1"
+"#
+    );
+
+    p.cargo("manifest sort-deps -Zunstable-options")
+        .masquerade_as_nightly_cargo()
+        .with_stderr("[UNCHANGED] [..]Cargo.toml")
+        .run();
+}
+
+#[cargo_test]
+fn check_min_rust_version() {
+    Package::new("old", "0.1.0")
+        .cargo_feature("rust-version")
+        .rust_version("1.40")
+        .publish();
+    Package::new("new", "0.1.0")
+        .cargo_feature("rust-version")
+        .rust_version("1.60.1")
+        .dep("old", "0.1")
+        .publish();
+    Package::new("plain", "0.1.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["rust-version"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                rust-version = "1.50"
+
+                [dependencies]
+                old = "0.1"
+                plain = "0.1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest check-min-rust-version -Zunstable-options")
+        .masquerade_as_nightly_cargo()
+        .with_stderr(
+            "\
+[UPDATING] [..]
+[DOWNLOADING] crates ...
+[DOWNLOADED] [..]
+[DOWNLOADED] [..]
+[CHECKED] dependencies of foo v0.1.0 ([CWD]) against rust-version 1.50
+",
+        )
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            cargo-features = ["rust-version"]
+
+            [package]
+            name = "foo"
+            version = "0.1.0"
+            rust-version = "1.50"
+
+            [dependencies]
+            new = "0.1"
+            plain = "0.1"
+        "#,
+    );
+
+    p.cargo("manifest check-min-rust-version -Zunstable-options")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[UPDATING] [..]
+[DOWNLOADING] crates ...
+[DOWNLOADED] [..]
+[ERROR] package `foo` has rust-version 1.50, but some of its dependencies require a newer Rust:
+  new v0.1.0 requires rust-version 1.60.1
+",
+        )
+        .run();
+}
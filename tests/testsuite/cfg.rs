@@ -174,6 +174,9 @@ fn bad_target_spec() {
             "\
 [ERROR] failed to parse manifest at `[..]`
 
+Caused by:
+  invalid platform `cfg(4)` in `[target]` table
+
 Caused by:
   failed to parse `4` as a cfg expression: unexpected character `4` in cfg, [..]
 ",
@@ -205,6 +208,9 @@ fn bad_target_spec2() {
             "\
 [ERROR] failed to parse manifest at `[..]`
 
+Caused by:
+  invalid platform `cfg(bar =)` in `[target]` table
+
 Caused by:
   failed to parse `bar =` as a cfg expression: expected a string, but cfg expression ended
 ",
@@ -392,7 +392,10 @@ lto = false
     let mut packages = BTreeMap::new();
     let key = toml::ProfilePackageSpec::Spec(::cargo::core::PackageIdSpec::parse("bar").unwrap());
     let o_profile = toml::TomlProfile {
-        opt_level: Some(toml::TomlOptLevel("2".to_string())),
+        opt_level: Some(toml::TomlOptLevel {
+            level: "2".to_string(),
+            was_string: false,
+        }),
         codegen_units: Some(9),
         ..Default::default()
     };
@@ -407,7 +410,10 @@ lto = false
     assert_eq!(
         p,
         toml::TomlProfile {
-            opt_level: Some(toml::TomlOptLevel("s".to_string())),
+            opt_level: Some(toml::TomlOptLevel {
+                level: "s".to_string(),
+                was_string: true,
+            }),
             lto: Some(toml::StringOrBool::Bool(true)),
             codegen_units: Some(5),
             debug: Some(toml::U32OrBool::Bool(true)),
@@ -418,7 +424,10 @@ lto = false
             incremental: Some(true),
             package: Some(packages),
             build_override: Some(Box::new(toml::TomlProfile {
-                opt_level: Some(toml::TomlOptLevel("1".to_string())),
+                opt_level: Some(toml::TomlOptLevel {
+                    level: "1".to_string(),
+                    was_string: false,
+                }),
                 codegen_units: Some(11),
                 ..Default::default()
             })),
@@ -728,7 +737,7 @@ Caused by:
   could not parse TOML configuration in `[..]/.cargo/config`
 
 Caused by:
-  could not parse input as TOML
+  could not parse input as TOML at `[..]/.cargo/config:1:5`
 
 Caused by:
   expected an equals, found eof at line 1 column 5",
@@ -1087,7 +1096,7 @@ Caused by:
   could not parse TOML configuration in `[..]/.cargo/config`
 
 Caused by:
-  could not parse input as TOML
+  could not parse input as TOML at `[..]/.cargo/config:2:15`
 
 Caused by:
   dotted key attempted to extend non-table type at line 2 column 15",
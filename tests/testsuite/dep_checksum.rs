@@ -0,0 +1,160 @@
+//! Tests for pinning the checksum of a dependency, the `dep-checksum`
+//! unstable feature.
+
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+
+fn manifest(dep: &str) -> String {
+    format!(
+        r#"
+            cargo-features = ["dep-checksum"]
+
+            [package]
+            name = "foo"
+            version = "0.0.1"
+
+            [dependencies]
+            bar = {}
+        "#,
+        dep
+    )
+}
+
+#[cargo_test]
+fn feature_gated() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = { version = "1.0", checksum = "00" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  feature `dep-checksum` is required
+
+  consider adding `cargo-features = [\"dep-checksum\"]` to the manifest
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn selects_version_with_checksum() {
+    let old = Package::new("bar", "1.0.0").publish();
+    Package::new("bar", "1.0.1").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &manifest(&format!("{{ version = \"1.0\", checksum = \"{}\" }}", old)),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile")
+        .masquerade_as_nightly_cargo()
+        .run();
+    let lockfile = p.read_lockfile();
+    assert!(lockfile.contains("version = \"1.0.0\""), "{}", lockfile);
+    assert!(!lockfile.contains("version = \"1.0.1\""), "{}", lockfile);
+}
+
+#[cargo_test]
+fn mismatched_checksum() {
+    Package::new("bar", "1.0.0").publish();
+    let checksum = "ab".repeat(32);
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &manifest(&format!(
+                "{{ version = \"1.0\", checksum = \"{}\" }}",
+                checksum
+            )),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(&format!(
+            "\
+[UPDATING] [..]
+[ERROR] failed to select a version for the requirement `bar = \"^1.0\"`
+candidate versions found which didn't match: 1.0.0
+location searched: `[..]` index (which is replacing registry `[..]`)
+the manifest also requires the checksum `{}`
+required by package `foo v0.0.1 ([..])`
+",
+            checksum
+        ))
+        .run();
+}
+
+#[cargo_test]
+fn invalid_checksum() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &manifest("{ version = \"1.0\", checksum = \"xyz\" }"),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  invalid `checksum` for dependency (bar): `xyz`
+  expected a SHA-256 checksum of 64 hexadecimal digits
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn path_dependency_is_rejected() {
+    let checksum = "ab".repeat(32);
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &manifest(&format!(
+                "{{ path = \"bar\", checksum = \"{}\" }}",
+                checksum
+            )),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  dependency (bar) sets `checksum`, which is only supported for registry \
+dependencies, not `git` or `path` dependencies
+",
+        )
+        .run();
+}
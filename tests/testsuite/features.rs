@@ -214,6 +214,101 @@ Caused by:
         .run();
 }
 
+#[cargo_test]
+fn feature_of_own_package() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [features]
+                extra = []
+                full = ["foo/extra"]
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  feature `full` includes `foo/extra`, but `foo` is the name of this package
+  To enable the `extra` feature of this package, write `\"extra\"` instead.
+",
+        )
+        .run();
+
+    // The name is compared with `-` and `_` treated the same.
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo-bar"
+            version = "0.0.1"
+
+            [features]
+            extra = []
+            full = ["foo_bar/extra"]
+        "#,
+    );
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  feature `full` includes `foo_bar/extra`, but `foo_bar` is the name of this package
+  To enable the `extra` feature of this package, write `\"extra\"` instead.
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn feature_of_dependency_named_like_own_package() {
+    // A dependency renamed to the package's own name is a real dependency.
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                foo = { path = "bar", package = "bar" }
+
+                [features]
+                full = ["foo/extra"]
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            "bar/Cargo.toml",
+            r#"
+                [package]
+                name = "bar"
+                version = "0.0.1"
+
+                [features]
+                extra = []
+            "#,
+        )
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("metadata --format-version 1").run();
+}
+
 #[cargo_test]
 fn invalid7() {
     let p = project()
@@ -1516,6 +1611,36 @@ fn warn_if_default_features() {
         ).run();
 }
 
+#[cargo_test]
+fn warn_if_default_is_empty() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+               [project]
+               name = "foo"
+               version = "0.0.1"
+               authors = []
+
+               [features]
+               default = []
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build")
+        .with_stderr(
+            "\
+[WARNING] `default = []` was found in [features]. \
+An empty `default` feature has no effect and can be removed.
+[COMPILING] foo v0.0.1 ([CWD])
+[FINISHED] dev [unoptimized + debuginfo] target(s) in [..]
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn no_feature_for_non_optional_dep() {
     let p = project()
@@ -1099,6 +1099,7 @@ fn proc_macro_ws() {
     p.cargo("check --workspace -v")
         .with_stderr(
             "\
+[NOTE] using resolver \"2\" selected by the `resolver` field in `[workspace]`
 [FRESH] foo v0.1.0 [..]
 [FRESH] pm v0.1.0 [..]
 [FINISHED] dev [..]
@@ -1433,6 +1434,116 @@ fn resolver_ws_root_and_member() {
         .run();
 }
 
+#[cargo_test]
+fn resolver_source() {
+    // Reports which part of the manifest selected the resolver.
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "foo"
+            version = "0.1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -v")
+        .with_stderr_does_not_contain("[NOTE] using resolver[..]")
+        .run();
+    p.cargo("metadata --no-deps")
+        .with_stdout_does_not_contain("[..]resolver_source[..]")
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+        [package]
+        name = "foo"
+        version = "0.1.0"
+        resolver = "2"
+        "#,
+    );
+    p.cargo("check -v")
+        .with_stderr_contains(
+            "[NOTE] using resolver \"2\" selected by the `resolver` field in `[package]`",
+        )
+        .run();
+    p.cargo("metadata --no-deps")
+        .with_stdout_contains("[..]\"resolver_source\":\"package-field\"[..]")
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+        [workspace]
+        resolver = "1"
+
+        [package]
+        name = "foo"
+        version = "0.1.0"
+        "#,
+    );
+    p.cargo("check -v")
+        .with_stderr_contains(
+            "[NOTE] using resolver \"1\" selected by the `resolver` field in `[workspace]`",
+        )
+        .run();
+    p.cargo("metadata --no-deps")
+        .with_stdout_contains("[..]\"resolver_source\":\"workspace-field\"[..]")
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+        cargo-features = ["edition2021"]
+
+        [package]
+        name = "foo"
+        version = "0.1.0"
+        edition = "2021"
+        "#,
+    );
+    p.cargo("check -v")
+        .masquerade_as_nightly_cargo()
+        .with_stderr_contains(
+            "[NOTE] using resolver \"2\" selected by the default for the package's edition",
+        )
+        .run();
+    p.cargo("metadata --no-deps")
+        .masquerade_as_nightly_cargo()
+        .with_stdout_contains("[..]\"resolver_source\":\"edition-default\"[..]")
+        .run();
+}
+
+#[cargo_test]
+fn virtual_resolver_source() {
+    // Virtual manifests can only select the resolver in `[workspace]`.
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [workspace]
+            members = ["a"]
+            resolver = "2"
+            "#,
+        )
+        .file("a/Cargo.toml", &basic_manifest("a", "0.1.0"))
+        .file("a/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -v")
+        .with_stderr_contains(
+            "[NOTE] using resolver \"2\" selected by the `resolver` field in `[workspace]`",
+        )
+        .run();
+    // The member did not select anything itself.
+    p.cargo("metadata --no-deps")
+        .with_stdout_does_not_contain("[..]resolver_source[..]")
+        .run();
+}
+
 #[cargo_test]
 fn resolver_enables_new_features() {
     // resolver="2" enables all the things.
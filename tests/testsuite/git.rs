@@ -2374,7 +2374,7 @@ Caused by:
   failed to parse manifest at `[..]`
 
 Caused by:
-  could not parse input as TOML
+  could not parse input as TOML at `[..]`
 
 Caused by:
   duplicate key: `categories` for key `project` at line 10 column 21",
@@ -3105,6 +3105,7 @@ fn metadata_master_consistency() {
                       "kind": null,
                       "rename": null,
                       "optional": false,
+                      "public": false,
                       "uses_default_features": true,
                       "features": [],
                       "target": null,
@@ -0,0 +1,247 @@
+//! Tests for `package.links-version`, the `links-version` unstable feature.
+
+use cargo_test_support::project;
+use cargo_test_support::registry::{self, Package};
+
+fn sys_manifest(name: &str, links_version: &str) -> String {
+    format!(
+        r#"
+            cargo-features = ["links-version"]
+
+            [package]
+            name = "{}"
+            version = "0.1.0"
+            links = "foo"
+            links-version = "{}"
+        "#,
+        name, links_version
+    )
+}
+
+#[cargo_test]
+fn feature_gated() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo-sys"
+                version = "0.1.0"
+                links = "foo"
+                links-version = "1.1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("build.rs", "fn main() {}")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  feature `links-version` is required
+
+  consider adding `cargo-features = [\"links-version\"]` to the manifest
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn different_major_versions() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [workspace]
+                members = ["a-sys", "b-sys"]
+            "#,
+        )
+        .file("a-sys/Cargo.toml", &sys_manifest("a-sys", "1.1.0"))
+        .file("a-sys/src/lib.rs", "")
+        .file("a-sys/build.rs", "fn main() {}")
+        .file("b-sys/Cargo.toml", &sys_manifest("b-sys", "3.0.2"))
+        .file("b-sys/src/lib.rs", "")
+        .file("b-sys/build.rs", "fn main() {}")
+        .build();
+
+    p.cargo("check").masquerade_as_nightly_cargo().run();
+
+    // Semver compatible versions of the library can't both be linked.
+    p.change_file("b-sys/Cargo.toml", &sys_manifest("b-sys", "1.4.0"));
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr_contains("[..]more than one crate with links=foo@1.")
+        .run();
+}
+
+#[cargo_test]
+fn compatible_versions_conflict() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["links-version"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                links = "foo"
+                links-version = "1.0.0"
+
+                [dependencies]
+                a-sys = { path = "a-sys" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("build.rs", "fn main() {}")
+        .file("a-sys/Cargo.toml", &sys_manifest("a-sys", "1.2.0"))
+        .file("a-sys/src/lib.rs", "")
+        .file("a-sys/build.rs", "fn main() {}")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+error: failed to select a version for `a-sys`.
+    ... required by package `foo v0.1.0 ([..])`
+versions that meet the requirements `*` are: 0.1.0
+
+the package `a-sys` links to the native library `foo@1`, but it conflicts with a previous package which links to `foo@1` as well:
+package `foo v0.1.0 ([..])`
+
+failed to select a version for `a-sys` which could resolve this conflict
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn requires_links() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["links-version"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                links-version = "1.0.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  `package.links-version` can only be used together with `package.links`
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn invalid_version() {
+    let p = project()
+        .file("Cargo.toml", &sys_manifest("foo-sys", "1.x"))
+        .file("src/lib.rs", "")
+        .file("build.rs", "fn main() {}")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  failed to parse `package.links-version`, expected a version like `1.1.0`, found `1.x`
+
+Caused by:
+  [..]
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn registry_dependencies() {
+    // The index records the versioned key, so registry packages that link
+    // to different major versions of the library can be used together.
+    for (name, links_version) in &[("a-sys", "1.1.0"), ("b-sys", "3.0.2")] {
+        let major = &links_version[..1];
+        Package::new(name, "0.1.0")
+            .links(&format!("foo@{}", major))
+            .file("Cargo.toml", &sys_manifest(name, links_version))
+            .file("src/lib.rs", "")
+            .file("build.rs", "fn main() {}")
+            .publish();
+    }
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [dependencies]
+                a-sys = "0.1"
+                b-sys = "0.1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check").masquerade_as_nightly_cargo().run();
+}
+
+#[cargo_test]
+fn published_links_key() {
+    registry::init();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["links-version"]
+
+                [package]
+                name = "foo-sys"
+                version = "0.1.0"
+                license = "MIT"
+                description = "foo"
+                links = "foo"
+                links-version = "3.0.2"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("build.rs", "fn main() {}")
+        .build();
+
+    p.cargo("publish --no-verify --token sekrit")
+        .masquerade_as_nightly_cargo()
+        .run();
+
+    let mut request = std::fs::File::open(registry::api_path().join("api/v1/crates/new")).unwrap();
+    let mut json_len = [0; 4];
+    std::io::Read::read_exact(&mut request, &mut json_len).unwrap();
+    let mut json = vec![0; u32::from_le_bytes(json_len) as usize];
+    std::io::Read::read_exact(&mut request, &mut json).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["links"], "foo@3", "{}", json);
+}
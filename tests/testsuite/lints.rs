@@ -0,0 +1,165 @@
+//! Tests for the `[lints]` table.
+
+use cargo_test_support::project;
+
+#[cargo_test]
+fn lints_gated() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [lints.rust]
+                dead_code = "warn"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  feature `lints` is required
+
+  consider adding `cargo-features = [\"lints\"]` to the manifest
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn lints_parse() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["lints"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [lints.rust]
+                dead_code = "allow"
+                unsafe_code = { level = "forbid", priority = -1 }
+
+                [lints.clippy]
+                pedantic = "warn"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .masquerade_as_nightly_cargo()
+        .with_stderr(
+            "\
+[COMPILING] foo [..]
+[FINISHED] [..]
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn invalid_lint_level() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["lints"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [lints.rust]
+                dead_code = "warning"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  `warning` is not a valid lint level, expected one of `forbid`, `deny`, `warn` or `allow` \
+for key `lints.rust.dead_code`
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn invalid_detailed_lint_level() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["lints"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [lints.rust]
+                dead_code = { level = 3 }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr_contains("  invalid type: integer `3`, expected a string[..]")
+        .run();
+}
+
+#[cargo_test]
+fn lint_name_with_tool_prefix() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["lints"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [workspace.lints.rust]
+                "clippy::pedantic" = "warn"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  invalid lint `rust::clippy::pedantic` in `[workspace.lints.rust]`, lint names are \
+written without a tool prefix, like `rust::dead_code` is written as `dead_code` \
+in `[workspace.lints.rust]`
+",
+        )
+        .run();
+}
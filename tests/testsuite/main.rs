@@ -11,8 +11,10 @@
 #[macro_use]
 extern crate cargo_test_macro;
 
+mod abi_version;
 mod advanced_env;
 mod alt_registry;
+mod artifact_dep;
 mod bad_config;
 mod bad_manifest_path;
 mod bench;
@@ -26,6 +28,7 @@ mod cargo_alias_config;
 mod cargo_command;
 mod cargo_env_config;
 mod cargo_features;
+mod cargo_manifest;
 mod cargo_targets;
 mod cfg;
 mod check;
@@ -41,6 +44,7 @@ mod cross_compile;
 mod cross_publish;
 mod custom_target;
 mod death;
+mod dep_checksum;
 mod dep_info;
 mod directory;
 mod doc;
@@ -62,6 +66,8 @@ mod init;
 mod install;
 mod install_upgrade;
 mod jobserver;
+mod links_version;
+mod lints;
 mod list_availables;
 mod local_registry;
 mod locate_project;
@@ -101,6 +107,7 @@ mod publish;
 mod publish_lockfile;
 mod read_manifest;
 mod registry;
+mod registry_version;
 mod rename_deps;
 mod replace;
 mod required_features;
@@ -320,6 +320,7 @@ fn cargo_metadata_with_deps_and_version() {
                         "req": "^0.0.1",
                         "source": "registry+https://github.com/rust-lang/crates.io-index",
                         "target": null,
+                        "public": false,
                         "uses_default_features": true
                     }
                 ],
@@ -411,6 +412,7 @@ fn cargo_metadata_with_deps_and_version() {
                         "req": "*",
                         "source": "registry+https://github.com/rust-lang/crates.io-index",
                         "target": null,
+                        "public": false,
                         "uses_default_features": true
                     },
                     {
@@ -423,9 +425,11 @@ fn cargo_metadata_with_deps_and_version() {
                         "req": "*",
                         "source": "registry+https://github.com/rust-lang/crates.io-index",
                         "target": null,
+                        "public": false,
                         "uses_default_features": true
                     }
                 ],
+                "deprecated_syntax": ["project-table"],
                 "description": "foo",
                 "edition": "2015",
                 "features": {},
@@ -1264,6 +1268,161 @@ fn package_metadata() {
         .run();
 }
 
+#[cargo_test]
+fn package_funding() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["package-funding"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [package.funding]
+                github = "octocat"
+                open-collective = "foo"
+                custom = ["https://example.com/donate"]
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("metadata --no-deps")
+        .masquerade_as_nightly_cargo()
+        .with_json(
+            r#"
+    {
+        "packages": [
+            {
+                "authors": [],
+                "categories": [],
+                "name": "foo",
+                "readme": null,
+                "repository": null,
+                "homepage": null,
+                "documentation": null,
+                "version": "0.1.0",
+                "id": "foo[..]",
+                "keywords": [],
+                "source": null,
+                "dependencies": [],
+                "edition": "2015",
+                "license": null,
+                "license_file": null,
+                "links": null,
+                "description": null,
+                "targets": "{...}",
+                "features": {},
+                "manifest_path": "[..]foo/Cargo.toml",
+                "metadata": null,
+                "publish": null,
+                "funding": {
+                    "github": ["octocat"],
+                    "patreon": null,
+                    "open-collective": "foo",
+                    "ko-fi": null,
+                    "liberapay": null,
+                    "custom": ["https://example.com/donate"]
+                }
+            }
+        ],
+        "workspace_members": ["foo[..]"],
+        "resolve": null,
+        "target_directory": "[..]foo/target",
+        "version": 1,
+        "workspace_root": "[..]/foo",
+        "metadata": null
+    }"#,
+        )
+        .run();
+}
+
+#[cargo_test]
+fn package_funding_invalid() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [package.funding]
+                github = "octocat"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("metadata --no-deps")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  feature `package-funding` is required
+
+  consider adding `cargo-features = [\"package-funding\"]` to the manifest
+",
+        )
+        .run();
+
+    let manifest = |funding: &str| {
+        format!(
+            r#"
+                cargo-features = ["package-funding"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [package.funding]
+                {}
+            "#,
+            funding
+        )
+    };
+
+    p.change_file(
+        "Cargo.toml",
+        &manifest(r#"github = ["octocat", "not/a/user"]"#),
+    );
+    p.cargo("metadata --no-deps")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  invalid GitHub username `not/a/user` in `package.funding.github`, \
+usernames may only contain ASCII letters, digits and `-`
+",
+        )
+        .run();
+
+    p.change_file("Cargo.toml", &manifest(r#"custom = "example.com/donate""#));
+    p.cargo("metadata --no-deps")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  invalid URL `example.com/donate` in `package.funding.custom`
+
+Caused by:
+  relative URL without a base
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn package_publish() {
     let p = project()
@@ -1737,6 +1896,7 @@ fn rename_dependency() {
                     "req": "^0.1.0",
                     "source": "registry+https://github.com/rust-lang/crates.io-index",
                     "target": null,
+                    "public": false,
                     "uses_default_features": true
                 },
                 {
@@ -1749,9 +1909,11 @@ fn rename_dependency() {
                     "req": "^0.2.0",
                     "source": "registry+https://github.com/rust-lang/crates.io-index",
                     "target": null,
+                    "public": false,
                     "uses_default_features": true
                 }
             ],
+            "deprecated_syntax": ["project-table"],
             "description": null,
             "edition": "2015",
             "features": {},
@@ -1872,6 +2034,7 @@ fn metadata_links() {
                   "authors": [],
                   "categories": [],
                   "dependencies": [],
+                  "deprecated_syntax": ["project-table"],
                   "description": null,
                   "edition": "2015",
                   "features": {},
@@ -1985,6 +2148,7 @@ fn deps_with_bin_only() {
                       "kind": null,
                       "rename": null,
                       "optional": false,
+                      "public": false,
                       "uses_default_features": true,
                       "path": "[..]/foo/bdep",
                       "features": [],
@@ -2270,6 +2434,7 @@ fn filter_platform() {
           "kind": null,
           "rename": null,
           "optional": false,
+          "public": false,
           "uses_default_features": true,
           "features": [],
           "target": null,
@@ -2282,6 +2447,7 @@ fn filter_platform() {
           "kind": null,
           "rename": null,
           "optional": false,
+          "public": false,
           "uses_default_features": true,
           "features": [],
           "target": "cfg(foobar)",
@@ -2294,6 +2460,7 @@ fn filter_platform() {
           "kind": null,
           "rename": null,
           "optional": false,
+          "public": false,
           "uses_default_features": true,
           "features": [],
           "target": "$ALT_TRIPLE",
@@ -2306,6 +2473,7 @@ fn filter_platform() {
           "kind": null,
           "rename": null,
           "optional": false,
+          "public": false,
           "uses_default_features": true,
           "features": [],
           "target": "$HOST_TRIPLE",
@@ -2979,3 +3147,96 @@ fn cargo_metadata_non_utf8() {
         .with_status(101)
         .run();
 }
+
+#[cargo_test]
+fn public_dependency() {
+    Package::new("pub_dep", "0.1.0").publish();
+    Package::new("priv_dep", "0.1.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["public-dependency"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                pub_dep = { version = "0.1.0", public = true }
+                priv_dep = "0.1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("metadata --no-deps")
+        .masquerade_as_nightly_cargo()
+        .with_json(
+            r#"
+{
+    "packages": [
+        {
+            "authors": [],
+            "categories": [],
+            "dependencies": [
+                {
+                    "features": [],
+                    "kind": null,
+                    "name": "priv_dep",
+                    "optional": false,
+                    "public": false,
+                    "registry": null,
+                    "rename": null,
+                    "req": "^0.1.0",
+                    "source": "registry+https://github.com/rust-lang/crates.io-index",
+                    "target": null,
+                    "uses_default_features": true
+                },
+                {
+                    "features": [],
+                    "kind": null,
+                    "name": "pub_dep",
+                    "optional": false,
+                    "public": true,
+                    "registry": null,
+                    "rename": null,
+                    "req": "^0.1.0",
+                    "source": "registry+https://github.com/rust-lang/crates.io-index",
+                    "target": null,
+                    "uses_default_features": true
+                }
+            ],
+            "description": null,
+            "documentation": null,
+            "edition": "2015",
+            "features": {},
+            "homepage": null,
+            "id": "foo 0.0.1 [..]",
+            "keywords": [],
+            "license": null,
+            "license_file": null,
+            "links": null,
+            "manifest_path": "[..]Cargo.toml",
+            "metadata": null,
+            "name": "foo",
+            "publish": null,
+            "readme": null,
+            "repository": null,
+            "source": null,
+            "targets": "{...}",
+            "version": "0.0.1"
+        }
+    ],
+    "resolve": null,
+    "target_directory": "[..]",
+    "version": 1,
+    "workspace_members": "{...}",
+    "workspace_root": "[..]",
+    "metadata": null
+}
+"#,
+        )
+        .run();
+}
@@ -1,5 +1,7 @@
 //! Tests for the `cargo package` command.
 
+use super::config::ConfigBuilder;
+use cargo::core::Workspace;
 use cargo_test_support::paths::CargoPathExt;
 use cargo_test_support::publish::validate_crate_contents;
 use cargo_test_support::registry::{self, Package};
@@ -294,6 +296,42 @@ dependency `bar` does not specify a version.
         .run();
 }
 
+#[cargo_test]
+fn registry_toml_rejects_path_only_dependency() {
+    // `cargo package` catches this in `verify_dependencies`, but the
+    // normalized manifest must not be produced for other callers either.
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [project]
+                name = "foo"
+                version = "0.0.1"
+
+                [target.'cfg(unix)'.dependencies]
+                bar = { path = "bar" }
+
+                [dev-dependencies]
+                baz = { path = "baz" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .file("baz/Cargo.toml", &basic_manifest("baz", "0.1.0"))
+        .file("baz/src/lib.rs", "")
+        .build();
+
+    let config = ConfigBuilder::new().build();
+    let ws = Workspace::new(&p.root().join("Cargo.toml"), &config).unwrap();
+    let err = ws.current().unwrap().to_registry_toml(&ws).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "all dependencies must have a version specified when publishing; \
+         dependency `bar` has only a path"
+    );
+}
+
 #[cargo_test]
 fn exclude() {
     let root = paths::root().join("exclude");
@@ -893,6 +931,179 @@ version = "1.0"
     );
 }
 
+#[cargo_test]
+fn generated_manifest_preserves_opt_level_quoting() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+                license = "MIT"
+                description = "foo"
+
+                [profile.dev]
+                opt-level = "1"
+
+                [profile.release]
+                opt-level = 2
+
+                [profile.bench]
+                opt-level = "s"
+            "#,
+        )
+        .file("src/main.rs", "")
+        .build();
+
+    p.cargo("package --no-verify").run();
+
+    let f = File::open(&p.root().join("target/package/foo-0.0.1.crate")).unwrap();
+    let rewritten_toml = format!(
+        r#"{}
+[package]
+name = "foo"
+version = "0.0.1"
+authors = []
+description = "foo"
+license = "MIT"
+[profile.bench]
+opt-level = "s"
+
+[profile.dev]
+opt-level = "1"
+
+[profile.release]
+opt-level = 2
+"#,
+        cargo::core::package::MANIFEST_PREAMBLE,
+    );
+
+    validate_crate_contents(
+        f,
+        "foo-0.0.1.crate",
+        &["Cargo.lock", "Cargo.toml", "Cargo.toml.orig", "src/main.rs"],
+        &[("Cargo.toml", &rewritten_toml)],
+    );
+}
+
+#[cargo_test]
+fn generated_manifest_uses_registry_version() {
+    let git_project = git::new("dep1", |project| {
+        project
+            .file("Cargo.toml", &basic_manifest("dep1", "1.0.0"))
+            .file("src/lib.rs", "")
+    });
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                cargo-features = ["registry-version"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+                license = "MIT"
+                description = "foo"
+
+                [dependencies]
+                dep1 = {{ version = "1.0", registry-version = "2.0", git = "{}" }}
+                "#,
+                git_project.url()
+            ),
+        )
+        .file("src/main.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_stderr_contains(
+            "[WARNING] dependency (dep1) requires `1.0` from git, but `2.0` when published; \
+             the versions are not semver compatible",
+        )
+        .run();
+    p.cargo("package --no-verify")
+        .masquerade_as_nightly_cargo()
+        .run();
+
+    let f = File::open(&p.root().join("target/package/foo-0.0.1.crate")).unwrap();
+    let rewritten_toml = format!(
+        r#"{}
+cargo-features = ["registry-version"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+authors = []
+description = "foo"
+license = "MIT"
+[dependencies.dep1]
+version = "2.0"
+"#,
+        cargo::core::package::MANIFEST_PREAMBLE,
+    );
+
+    validate_crate_contents(
+        f,
+        "foo-0.0.1.crate",
+        &["Cargo.lock", "Cargo.toml", "Cargo.toml.orig", "src/main.rs"],
+        &[("Cargo.toml", &rewritten_toml)],
+    );
+}
+
+#[cargo_test]
+fn generated_manifest_skips_empty_profiles() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+                license = "MIT"
+                description = "foo"
+
+                [profile.dev]
+
+                [profile.release]
+                opt-level = 2
+
+                [profile.test]
+            "#,
+        )
+        .file("src/main.rs", "")
+        .build();
+
+    p.cargo("package --no-verify").run();
+
+    let f = File::open(&p.root().join("target/package/foo-0.0.1.crate")).unwrap();
+    let rewritten_toml = format!(
+        r#"{}
+[package]
+name = "foo"
+version = "0.0.1"
+authors = []
+description = "foo"
+license = "MIT"
+[profile.release]
+opt-level = 2
+"#,
+        cargo::core::package::MANIFEST_PREAMBLE,
+    );
+
+    validate_crate_contents(
+        f,
+        "foo-0.0.1.crate",
+        &["Cargo.lock", "Cargo.toml", "Cargo.toml.orig", "src/main.rs"],
+        &[("Cargo.toml", &rewritten_toml)],
+    );
+}
+
 #[cargo_test]
 fn ignore_workspace_specifier() {
     let p = project()
@@ -1083,6 +1294,65 @@ Caused by:
         .run();
 }
 
+#[cargo_test]
+fn test_edition_integer() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+                edition = 2018
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+error: failed to parse manifest at `[..]`
+
+Caused by:
+  editions must be written as strings, e.g. `edition = \"2018\"` for key `package.edition`
+",
+        )
+        .run();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+
+                [[bin]]
+                name = "foo"
+                path = "src/main.rs"
+                edition = 2015
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+error: failed to parse manifest at `[..]`
+
+Caused by:
+  editions must be written as strings, e.g. `edition = \"2015\"`[..]
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn do_not_package_if_src_was_modified() {
     let p = project()
@@ -1954,3 +2224,106 @@ fn reproducible_output() {
         assert_eq!(header.groupname().unwrap().unwrap(), "");
     }
 }
+
+#[cargo_test]
+fn publish_strip_metadata() {
+    registry::init();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["publish-strip-metadata"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                license = "MIT"
+                description = "foo"
+                homepage = "foo"
+                publish-strip-metadata = ["internal", "ci", "missing"]
+
+                [package.metadata.internal]
+                owners = ["team-a"]
+
+                [package.metadata.docs]
+                all-features = true
+
+                [badges]
+                ci = { repository = "foo/foo" }
+                maintenance = { status = "passively-maintained" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_stderr_contains(
+            "\
+[WARNING] `package.publish-strip-metadata` entry `missing` does not match any key \
+in `[package.metadata]` or `[badges]`",
+        )
+        .run();
+
+    p.cargo("package --no-verify")
+        .masquerade_as_nightly_cargo()
+        .with_stderr(
+            "\
+[NOTE] leaving `package.metadata.internal`, `badges.ci` out of the published manifest
+[PACKAGING] foo v0.0.1 ([CWD])
+",
+        )
+        .run();
+
+    let f = File::open(&p.root().join("target/package/foo-0.0.1.crate")).unwrap();
+    let mut archive = Archive::new(GzDecoder::new(f));
+    let mut published = String::new();
+    let mut orig = String::new();
+    for ent in archive.entries().unwrap() {
+        let mut ent = ent.unwrap();
+        if ent.path().unwrap().ends_with("foo-0.0.1/Cargo.toml") {
+            std::io::Read::read_to_string(&mut ent, &mut published).unwrap();
+        } else if ent.path().unwrap().ends_with("foo-0.0.1/Cargo.toml.orig") {
+            std::io::Read::read_to_string(&mut ent, &mut orig).unwrap();
+        }
+    }
+    assert!(
+        published.contains("[package.metadata.docs]"),
+        "{}",
+        published
+    );
+    assert!(published.contains("[badges.maintenance]"), "{}", published);
+    assert!(!published.contains("internal"), "{}", published);
+    assert!(!published.contains("[badges.ci]"), "{}", published);
+    assert!(
+        !published.contains("publish-strip-metadata = "),
+        "{}",
+        published
+    );
+
+    // The original manifest in the package is stripped the same way.
+    assert!(orig.contains("[package.metadata.docs]"), "{}", orig);
+    assert!(orig.contains("maintenance = "), "{}", orig);
+    assert!(!orig.contains("internal"), "{}", orig);
+    assert!(!orig.contains("team-a"), "{}", orig);
+    assert!(!orig.contains("ci = "), "{}", orig);
+
+    // So are the badges sent to the registry.
+    p.cargo("publish --no-verify --token sekrit")
+        .masquerade_as_nightly_cargo()
+        .run();
+    let mut request = File::open(registry::api_path().join("api/v1/crates/new")).unwrap();
+    let mut json_len = [0; 4];
+    std::io::Read::read_exact(&mut request, &mut json_len).unwrap();
+    let mut json = vec![0; u32::from_le_bytes(json_len) as usize];
+    std::io::Read::read_exact(&mut request, &mut json).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let badges = json["badges"].as_object().unwrap();
+    assert!(badges.contains_key("maintenance"), "{}", json);
+    assert!(!badges.contains_key("ci"), "{}", json);
+    assert!(!json.to_string().contains("team-a"), "{}", json);
+
+    // The local manifest is left alone.
+    let local = p.read_file("Cargo.toml");
+    assert!(local.contains("[package.metadata.internal]"));
+}
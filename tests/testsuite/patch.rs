@@ -230,6 +230,7 @@ fn unused() {
     p.cargo("build")
         .with_stderr(
             "\
+[WARNING] patch for `bar` version 0.2.0 does not satisfy the dependency requirement `^0.1.0`
 [UPDATING] `[ROOT][..]` index
 [WARNING] Patch `bar v0.2.0 ([CWD]/bar)` was not used in the crate graph.
 [..]
@@ -247,6 +248,7 @@ fn unused() {
     p.cargo("build")
         .with_stderr(
             "\
+[WARNING] patch for `bar` version 0.2.0 does not satisfy the dependency requirement `^0.1.0`
 [WARNING] Patch `bar v0.2.0 ([CWD]/bar)` was not used in the crate graph.
 [..]
 [..]
@@ -268,6 +270,128 @@ fn unused() {
     );
 }
 
+#[cargo_test]
+fn unused_strict_patch_versions() {
+    Package::new("bar", "0.1.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+
+                [dependencies]
+                bar = "0.1.0"
+
+                [patch.crates-io]
+                bar = { path = "bar" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.2.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build -Zstrict-patch-versions")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] patch for `bar` version 0.2.0 does not satisfy the dependency requirement `^0.1.0`
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn strict_patch_versions_legacy_toml() {
+    // The patch is loaded like any other manifest, so syntax that Cargo
+    // only warns about does not hide the version.
+    Package::new("bar", "0.1.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = "0.1.0"
+
+                [patch.crates-io]
+                bar = { path = "bar" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package] name = "bar"
+version = "0.2.0"
+"#,
+        )
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build -Zstrict-patch-versions")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr_contains(
+            "[ERROR] patch for `bar` version 0.2.0 does not satisfy the dependency \
+             requirement `^0.1.0`",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn strict_patch_versions_ignores_non_root_patch() {
+    // Only the workspace root's `[patch]` is applied, so the one in a
+    // dependency's manifest is not checked.
+    Package::new("bar", "0.1.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                baz = { path = "baz" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            "baz/Cargo.toml",
+            r#"
+                [package]
+                name = "baz"
+                version = "0.1.0"
+
+                [dependencies]
+                bar = "0.1.0"
+
+                [patch.crates-io]
+                bar = { path = "bar" }
+            "#,
+        )
+        .file("baz/src/lib.rs", "")
+        .file("baz/bar/Cargo.toml", &basic_manifest("bar", "0.2.0"))
+        .file("baz/bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build -Zstrict-patch-versions")
+        .masquerade_as_nightly_cargo()
+        .with_stderr_does_not_contain("[..]does not satisfy[..]")
+        .run();
+}
+
 #[cargo_test]
 fn unused_git() {
     Package::new("bar", "0.1.0").publish();
@@ -1653,6 +1777,7 @@ fn mismatched_version() {
         .with_status(101)
         .with_stderr(
             "\
+[WARNING] patch for `bar` version 0.1.0 does not satisfy the dependency requirement `^0.1.1`
 [ERROR] failed to resolve patches for `https://github.com/rust-lang/crates.io-index`
 
 Caused by:
@@ -1135,3 +1135,46 @@ fn catch_tricky_cycle() {
         .with_status(101)
         .run();
 }
+
+#[cargo_test]
+fn same_path_dep_spelled_differently() {
+    // Every spelling of the same directory should resolve to a single
+    // `SourceId`, so the package is only built once.
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.5.0"
+                authors = []
+
+                [dependencies]
+                bar = { path = "bar" }
+
+                [dev-dependencies]
+                bar = { path = "./bar/" }
+
+                [build-dependencies]
+                bar = { path = "baz/../bar" }
+            "#,
+        )
+        .file("src/lib.rs", "extern crate bar;")
+        .file("build.rs", "extern crate bar; fn main() {}")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.5.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_stderr(
+            "\
+[COMPILING] bar v0.5.0 ([CWD]/bar)
+[COMPILING] foo v0.5.0 ([CWD])
+[FINISHED] [..]
+",
+        )
+        .run();
+    p.cargo("pkgid bar")
+        .with_stdout("file://[..]/foo/bar#0.5.0")
+        .run();
+}
@@ -129,7 +129,7 @@ fn profile_config_validate_errors() {
 [ERROR] config profile `dev` is not valid (defined in `[..]/foo/.cargo/config`)
 
 Caused by:
-  `panic` may not be specified in a `package` profile
+  `panic` may not be specified in a `package` profile, found in `[profile.dev.package.\"foo\"]`
 ",
         )
         .run();
@@ -195,11 +195,48 @@ fn non_existent_inherits() {
         .with_stderr(
             "\
 [ERROR] profile `release-lto` inherits from `non-existent`, but that profile is not defined
+
+Defined profiles: `dev`, `release`, `test`, `bench`, `check`, `doc`, `release-lto`
 ",
         )
         .run();
 }
 
+#[cargo_test]
+fn non_existent_inherits_in_dependency() {
+    // Profiles of dependencies are ignored, so their `inherits` isn't checked.
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [dependencies]
+                bar = { path = "bar" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            "bar/Cargo.toml",
+            r#"
+                cargo-features = ["named-profiles"]
+
+                [package]
+                name = "bar"
+                version = "0.0.1"
+
+                [profile.release-lto]
+                inherits = "non-existent"
+            "#,
+        )
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build").masquerade_as_nightly_cargo().run();
+}
+
 #[cargo_test]
 fn self_inherits() {
     let p = project()
@@ -226,7 +263,7 @@ fn self_inherits() {
         .with_status(101)
         .with_stderr(
             "\
-[ERROR] profile inheritance loop detected with profile `release-lto` inheriting `release-lto`
+[ERROR] profile `release-lto` inherits from itself; `inherits` must name a different profile
 ",
         )
         .run();
@@ -496,3 +533,81 @@ fn unknown_profile() {
         .with_status(101)
         .run();
 }
+
+#[cargo_test]
+fn merge_policy() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["named-profiles"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+
+                [profile.dev]
+                codegen-units = 3
+
+                [profile.fill]
+                inherits = "dev"
+                merge-policy = "if-unset"
+                codegen-units = 7
+                opt-level = 1
+
+                [profile.replace]
+                inherits = "dev"
+                merge-policy = "override"
+                codegen-units = 7
+                opt-level = 1
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build --profile=fill -Z unstable-options -v")
+        .masquerade_as_nightly_cargo()
+        .with_stderr_contains(
+            "[RUNNING] `rustc --crate-name foo [..]-C opt-level=1[..]-C codegen-units=3 [..]`",
+        )
+        .run();
+
+    p.cargo("build --profile=replace -Z unstable-options -v")
+        .masquerade_as_nightly_cargo()
+        .with_stderr_contains(
+            "[RUNNING] `rustc --crate-name foo [..]-C opt-level=1[..]-C codegen-units=7 [..]`",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn merge_policy_on_root_profile() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["named-profiles"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+
+                [profile.release]
+                merge-policy = "if-unset"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] `merge-policy` must not be specified in root profile `release`
+",
+        )
+        .run();
+}
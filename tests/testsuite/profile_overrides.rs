@@ -40,6 +40,78 @@ fn profile_override_basic() {
         .run();
 }
 
+#[cargo_test]
+fn profile_package_defaults() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["profile-package-defaults"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+
+                [dependencies]
+                bar = {path = "bar"}
+
+                [profile.dev]
+                opt-level = 1
+                package-defaults = 3
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_lib_manifest("bar"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build -v")
+        .masquerade_as_nightly_cargo()
+        .with_stderr(
+            "[COMPILING] bar [..]
+[RUNNING] `rustc --crate-name bar [..] -C opt-level=3 [..]`
+[COMPILING] foo [..]
+[RUNNING] `rustc --crate-name foo [..] -C opt-level=1 [..]`
+[FINISHED] dev [optimized + debuginfo] target(s) in [..]",
+        )
+        .run();
+
+    // An explicit `package."*"` table wins over the shorthand.
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            cargo-features = ["profile-package-defaults"]
+
+            [package]
+            name = "foo"
+            version = "0.0.1"
+            authors = []
+
+            [dependencies]
+            bar = {path = "bar"}
+
+            [profile.dev]
+            opt-level = 1
+            package-defaults = 3
+
+            [profile.dev.package."*"]
+            opt-level = 2
+        "#,
+    );
+
+    p.cargo("build -v")
+        .masquerade_as_nightly_cargo()
+        .with_stderr(
+            "[COMPILING] bar [..]
+[RUNNING] `rustc --crate-name bar [..] -C opt-level=2 [..]`
+[COMPILING] foo [..]
+[RUNNING] `rustc --crate-name foo [..] -C opt-level=1 [..]`
+[FINISHED] dev [optimized + debuginfo] target(s) in [..]",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn profile_override_warnings() {
     let p = project()
@@ -89,15 +161,18 @@ fn profile_override_bad_settings() {
     let bad_values = [
         (
             "panic = \"abort\"",
-            "`panic` may not be specified in a `package` profile",
+            "`panic` may not be specified in a `package` profile, \
+             found in `[profile.dev.package.\"bar\"]`",
         ),
         (
             "lto = true",
-            "`lto` may not be specified in a `package` profile",
+            "`lto` may not be specified in a `package` profile, \
+             found in `[profile.dev.package.\"bar\"]`",
         ),
         (
             "rpath = true",
-            "`rpath` may not be specified in a `package` profile",
+            "`rpath` may not be specified in a `package` profile, \
+             found in `[profile.dev.package.\"bar\"]`",
         ),
         ("package = {}", "package-specific profiles cannot be nested"),
     ];
@@ -132,6 +207,37 @@ fn profile_override_bad_settings() {
     }
 }
 
+#[cargo_test]
+fn profile_override_bad_settings_build_override() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [profile.release.build-override]
+                lto = true
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  `lto` may not be specified in a `build-override` profile, \
+found in `[profile.release.build-override]`
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn profile_override_hierarchy() {
     // Test that the precedence rules are correct for different types.
@@ -2,7 +2,7 @@
 
 use std::env;
 
-use cargo_test_support::{is_nightly, project};
+use cargo_test_support::{basic_manifest, is_nightly, project};
 
 #[cargo_test]
 fn profile_overrides() {
@@ -534,7 +534,7 @@ fn strip_requires_cargo_feature() {
         .with_status(101)
         .with_stderr(
             "\
-[ERROR] failed to parse manifest at `[CWD]/Cargo.toml`
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml:6:17`
 
 Caused by:
   feature `strip` is required
@@ -618,6 +618,78 @@ fn strip_accepts_true_to_strip_symbols() {
         .run();
 }
 
+#[cargo_test]
+// Strip doesn't work on macos.
+#[cfg_attr(target_os = "macos", ignore)]
+fn strip_in_package_override() {
+    if !is_nightly() {
+        // -Zstrip is unstable
+        return;
+    }
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["strip"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [dependencies]
+                bar = { path = "bar" }
+
+                [profile.release.package.bar]
+                strip = 'symbols'
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build --release -v")
+        .masquerade_as_nightly_cargo()
+        .with_stderr_contains("[RUNNING] `rustc --crate-name bar [..] -Z strip=symbols [..]`")
+        .with_stderr_line_without(&["[RUNNING] `rustc --crate-name foo"], &["-Z strip"])
+        .run();
+}
+
+#[cargo_test]
+fn strip_in_build_override_is_rejected() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["strip"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [profile.release.build-override]
+                strip = 'symbols'
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build --release")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml:8:17`
+
+Caused by:
+  `strip` may not be specified in a `build-override` profile, \
+found in `[profile.release.build-override]`
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn strip_accepts_false_to_disable_strip() {
     if !is_nightly() {
@@ -646,3 +718,178 @@ fn strip_accepts_false_to_disable_strip() {
         .with_stderr_does_not_contain("-Z strip")
         .run();
 }
+
+#[cargo_test]
+fn self_profile_works() {
+    if !is_nightly() {
+        // -Zself-profile is unstable
+        return;
+    }
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["profile-self-profile"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [profile.release]
+                self-profile = true
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build --release -v")
+        .masquerade_as_nightly_cargo()
+        .with_stderr(
+            "\
+[COMPILING] foo [..]
+[RUNNING] `rustc [..] -Z self-profile=[CWD]/target/profile-data [..]`
+[FINISHED] [..]
+",
+        )
+        .run();
+    assert!(p.root().join("target/profile-data").is_dir());
+
+    p.cargo("build -v")
+        .masquerade_as_nightly_cargo()
+        .with_stderr_does_not_contain("[..]-Z self-profile[..]")
+        .run();
+}
+
+#[cargo_test]
+fn self_profile_requires_cargo_feature() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [profile.dev.package."*"]
+                self-profile = true
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml:6:17`
+
+Caused by:
+  feature `profile-self-profile` is required
+
+  consider adding `cargo-features = [\"profile-self-profile\"]` to the manifest
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn trim_paths_requires_cargo_feature() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [profile.release]
+                trim-paths = "all"
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build --release")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml:6:17`
+
+Caused by:
+  feature `trim-paths` is required
+
+  consider adding `cargo-features = [\"trim-paths\"]` to the manifest
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn trim_paths_rejects_unknown_value() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["trim-paths"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [profile.release]
+                trim-paths = ["object", "everything"]
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build --release")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml:8:17`
+
+Caused by:
+  `trim-paths` setting of `everything` is not a valid setting, \
+must be `none`, `all`, `object`, `diagnostics`, or a path
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn trim_paths_in_package_override() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["trim-paths"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [profile.release]
+                trim-paths = "none"
+
+                [profile.release.package."*"]
+                trim-paths = ["diagnostics", "./src"]
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build --release")
+        .masquerade_as_nightly_cargo()
+        .with_stderr(
+            "\
+[COMPILING] foo [..]
+[FINISHED] [..]
+",
+        )
+        .run();
+}
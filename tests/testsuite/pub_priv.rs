@@ -150,6 +150,9 @@ fn requires_feature() {
             "\
 error: failed to parse manifest at `[..]`
 
+Caused by:
+  dependency (pub_dep) sets `public`, which is feature gated
+
 Caused by:
   feature `public-dependency` is required
 
@@ -196,7 +199,42 @@ fn pub_dev_dependency() {
 error: failed to parse manifest at `[..]`
 
 Caused by:
-  'public' specifier can only be used on regular dependencies, not Development dependencies
+  'public' can only be set on normal dependencies; `pub_dep` is a dev-dependency
+",
+        )
+        .run()
+}
+
+#[cargo_test]
+fn pub_build_dependency() {
+    Package::new("pub_dep", "0.1.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["public-dependency"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [target.'cfg(unix)'.build-dependencies]
+                pub_dep = {version = "0.1.0", public = true}
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build --message-format=short")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+error: failed to parse manifest at `[..]`
+
+Caused by:
+  'public' can only be set on normal dependencies; `pub_dep` is a build-dependency
 ",
         )
         .run()
@@ -675,6 +675,112 @@ The registry `alternative` is not listed in the `publish` value in Cargo.toml.
         .run();
 }
 
+#[cargo_test]
+fn publish_bare_string() {
+    registry::init();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [project]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+                license = "MIT"
+                description = "foo"
+                publish = "test"
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build")
+        .with_stderr_contains(
+            "\
+[WARNING] `publish = \"test\"` is treated as `publish = [\"test\"]`; \
+write it as an array to silence this warning",
+        )
+        .run();
+
+    p.cargo("publish --registry alternative")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] `foo` cannot be published.
+The registry `alternative` is not listed in the `publish` value in Cargo.toml.
+",
+        )
+        .run();
+
+    // The published manifest uses the array form that older Cargo accepts.
+    p.cargo("package --no-verify").run();
+    let f = fs::File::open(p.root().join("target/package/foo-0.0.1.crate")).unwrap();
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(f));
+    let mut published = String::new();
+    for ent in archive.entries().unwrap() {
+        let mut ent = ent.unwrap();
+        if ent.path().unwrap().ends_with("foo-0.0.1/Cargo.toml") {
+            std::io::Read::read_to_string(&mut ent, &mut published).unwrap();
+        }
+    }
+    assert!(published.contains("publish = [\"test\"]"), "{}", published);
+}
+
+#[cargo_test]
+fn publish_invalid_list() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [project]
+                name = "foo"
+                version = "0.0.1"
+                authors = []
+                publish = [true]
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("publish")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  `publish` must be an array of registry names, but it contains the boolean `true` \
+for key `project.publish`
+",
+        )
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [project]
+            name = "foo"
+            version = "0.0.1"
+            authors = []
+            publish = ["crates-io", false]
+        "#,
+    );
+
+    p.cargo("publish")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  `publish` cannot mix registry names with `false`; use `publish = false` \
+to prevent publishing for key `project.publish`
+",
+        )
+        .run();
+}
+
 #[cargo_test]
 fn publish_allowed_registry() {
     registry::alt_init();
@@ -1,5 +1,8 @@
 //! Tests for the `cargo read-manifest` command.
 
+use super::config::ConfigBuilder;
+use cargo::core::{DeprecatedSyntax, EitherManifest, SourceId, WarningCategory};
+use cargo::util::toml::{manifest_is_current, read_manifest, U32OrBool};
 use cargo_test_support::{basic_bin_manifest, main_file, project};
 
 fn manifest_output(readme_value: &str) -> String {
@@ -173,6 +176,37 @@ fn cargo_read_manifest_default_readme() {
     }
 }
 
+#[cargo_test]
+fn cargo_read_manifest_lowercase_default_readme() {
+    for (readme, conventional) in &[
+        ("readme.md", "README.md"),
+        ("readme.txt", "README.txt"),
+        ("readme", "README"),
+    ] {
+        let p = project()
+            .file("Cargo.toml", &basic_bin_manifest("foo"))
+            .file(readme, "Sample project")
+            .file("src/foo.rs", &main_file(r#""i am foo""#, &[]))
+            .build();
+
+        if p.root().join(conventional).is_file() {
+            // Case-insensitive file system, the uppercase name is found first.
+            return;
+        }
+
+        p.cargo("read-manifest")
+            .with_json(&manifest_output(&format!(r#""{}""#, readme)))
+            .run();
+        p.cargo("check")
+            .with_stderr_contains(&format!(
+                "[WARNING] using `{}` as the package README; consider renaming it to `{}` \
+                 so that it is found the same way on every file system",
+                readme, conventional
+            ))
+            .run();
+    }
+}
+
 #[cargo_test]
 fn cargo_read_manifest_suppress_default_readme() {
     let p = project()
@@ -202,3 +236,311 @@ fn cargo_read_manifest_defaults_readme_if_true() {
         .with_json(&manifest_output(r#""README.md""#))
         .run();
 }
+
+#[cargo_test]
+fn manifest_contents_hash() {
+    let p = project()
+        .file("Cargo.toml", &basic_bin_manifest("foo"))
+        .file("src/foo.rs", &main_file(r#""i am foo""#, &[]))
+        .build();
+    let config = ConfigBuilder::new().build();
+    let path = p.root().join("Cargo.toml");
+    let source_id = SourceId::for_path(&p.root()).unwrap();
+    let read_hash = || match read_manifest(&path, source_id, &config).unwrap().0 {
+        EitherManifest::Real(manifest) => manifest.contents_hash(),
+        EitherManifest::Virtual(_) => panic!("expected a real manifest"),
+    };
+
+    // The hash only depends on the contents.
+    let hash = read_hash();
+    assert_eq!(hash, read_hash());
+    assert!(manifest_is_current(&path, hash).unwrap());
+
+    p.change_file(
+        "Cargo.toml",
+        &format!("# edited\n{}", basic_bin_manifest("foo")),
+    );
+    assert!(!manifest_is_current(&path, hash).unwrap());
+    let new_hash = read_hash();
+    assert_ne!(hash, new_hash);
+    assert!(manifest_is_current(&path, new_hash).unwrap());
+
+    let err = manifest_is_current(&p.root().join("missing/Cargo.toml"), hash).unwrap_err();
+    assert!(err.to_string().starts_with("failed to open manifest at `"));
+}
+
+#[cargo_test]
+fn manifest_is_publishable() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [dependencies]
+                bar = { path = "bar", version = "0.1.0" }
+
+                [dev-dependencies]
+                baz = { path = "baz" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    let config = ConfigBuilder::new().build();
+    let path = p.root().join("Cargo.toml");
+    let source_id = SourceId::for_path(&p.root()).unwrap();
+    let is_publishable = || match read_manifest(&path, source_id, &config).unwrap().0 {
+        EitherManifest::Real(manifest) => manifest.original().is_publishable(),
+        EitherManifest::Virtual(_) => panic!("expected a real manifest"),
+    };
+
+    // Dev-dependencies without a version are stripped on publish.
+    assert!(is_publishable());
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            version = "0.1.0"
+            publish = false
+        "#,
+    );
+    assert!(!is_publishable());
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            version = "0.1.0"
+
+            [target.'cfg(unix)'.build-dependencies]
+            bar = { path = "bar" }
+        "#,
+    );
+    assert!(!is_publishable());
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            version = "0.1.0"
+
+            [dependencies]
+            bar = { git = "https://example.com/bar" }
+        "#,
+    );
+    assert!(!is_publishable());
+}
+
+#[cargo_test]
+fn manifest_resolved_profile() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["named-profiles"]
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [profile.dev]
+                debug = 1
+                incremental = false
+
+                [profile.custom]
+                inherits = "dev"
+                opt-level = 2
+
+                [profile.child]
+                inherits = "custom"
+                incremental = true
+
+                [profile.loop-a]
+                inherits = "loop-b"
+
+                [profile.loop-b]
+                inherits = "loop-a"
+
+                [profile.orphan]
+                inherits = "missing"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    let config = ConfigBuilder::new().nightly_features_allowed(true).build();
+    let path = p.root().join("Cargo.toml");
+    let source_id = SourceId::for_path(&p.root()).unwrap();
+    let manifest = match read_manifest(&path, source_id, &config).unwrap().0 {
+        EitherManifest::Real(manifest) => manifest,
+        EitherManifest::Virtual(_) => panic!("expected a real manifest"),
+    };
+    let toml = manifest.original();
+
+    let child = toml.resolved_profile("child").unwrap().unwrap();
+    assert_eq!(child.opt_level.unwrap().level, "2");
+    assert_eq!(child.debug, Some(U32OrBool::U32(1)));
+    assert_eq!(child.incremental, Some(true));
+    assert_eq!(child.inherits.unwrap(), "custom");
+
+    assert!(toml.resolved_profile("release").unwrap().is_none());
+
+    let err = toml.resolved_profile("loop-a").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "profile inheritance loop detected with profile `loop-b` inheriting `loop-a`"
+    );
+    let err = toml.resolved_profile("orphan").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "profile `orphan` inherits from `missing`, but that profile is not defined"
+    );
+}
+
+#[cargo_test]
+fn manifest_deprecated_syntax() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [project]
+                name = "foo"
+                version = "0.1.0"
+
+                [dev_dependencies]
+                baz = { path = "baz" }
+
+                [replace]
+                "bar:0.1.0" = { path = "bar" }
+
+                [badges]
+                maintenance = { status = "passively-maintained" }
+                travis-ci = { repository = "foo/foo" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    let config = ConfigBuilder::new().build();
+    let path = p.root().join("Cargo.toml");
+    let source_id = SourceId::for_path(&p.root()).unwrap();
+    let deprecated_syntax = || match read_manifest(&path, source_id, &config).unwrap().0 {
+        EitherManifest::Real(manifest) => manifest.deprecated_syntax(),
+        EitherManifest::Virtual(_) => panic!("expected a real manifest"),
+    };
+
+    let found = deprecated_syntax();
+    assert_eq!(
+        found,
+        DeprecatedSyntax::PROJECT_TABLE
+            | DeprecatedSyntax::UNDERSCORE_KEYS
+            | DeprecatedSyntax::REPLACE
+            | DeprecatedSyntax::BADGES
+    );
+    assert_eq!(
+        found.names().collect::<Vec<_>>(),
+        ["project-table", "underscore-keys", "replace", "badges"]
+    );
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            version = "0.1.0"
+
+            [dev-dependencies]
+            baz = { path = "baz" }
+
+            [badges]
+            maintenance = { status = "passively-maintained" }
+        "#,
+    );
+    assert!(deprecated_syntax().is_empty());
+}
+
+#[cargo_test]
+fn manifest_warnings_by_category() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "Foo"
+                version = "0.1.0"
+                unknown-key = true
+
+                [dependencies]
+                bar = { path = "bar", branch = "main" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    let config = ConfigBuilder::new().build();
+    let source_id = SourceId::for_path(&p.root()).unwrap();
+    let mut manifest = match read_manifest(&p.root().join("Cargo.toml"), source_id, &config)
+        .unwrap()
+        .0
+    {
+        EitherManifest::Real(manifest) => manifest,
+        EitherManifest::Virtual(_) => panic!("expected a real manifest"),
+    };
+
+    // Warnings that were already reported aren't added again.
+    let first = manifest.warnings().warnings()[0].clone();
+    manifest
+        .warnings_mut()
+        .push(first.category, first.message.clone());
+    assert_eq!(manifest.warnings().warnings().len(), 3);
+
+    let grouped = manifest.warnings().by_category();
+    let categories: Vec<_> = grouped.iter().map(|(c, _)| c.as_str()).collect();
+    assert_eq!(categories, ["unused-key", "dependency", "metadata"]);
+    assert_eq!(
+        grouped[0].1[0].message,
+        "unused manifest key: package.unknown-key"
+    );
+    assert!(grouped[1].1[0]
+        .message
+        .starts_with("key `branch` is ignored for dependency (bar)"));
+    assert!(grouped[2].1[0]
+        .message
+        .starts_with("package name `Foo` contains uppercase characters"));
+    assert!(grouped
+        .iter()
+        .all(|(category, _)| *category != WarningCategory::Other));
+}
+
+#[cargo_test]
+fn manifest_toml_syntax_warnings() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            "[package] name = \"foo\"\nversion = \"0.1.0\"\n",
+        )
+        .file("src/lib.rs", "")
+        .build();
+    let config = ConfigBuilder::new().build();
+    let source_id = SourceId::for_path(&p.root()).unwrap();
+    let manifest = match read_manifest(&p.root().join("Cargo.toml"), source_id, &config)
+        .unwrap()
+        .0
+    {
+        EitherManifest::Real(manifest) => manifest,
+        EitherManifest::Virtual(_) => panic!("expected a real manifest"),
+    };
+
+    // The warning is printed while reading, and recorded on the manifest so
+    // that `Workspace::emit_warnings` doesn't print it again.
+    let warnings = manifest.warnings().warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].category, WarningCategory::Deprecated);
+    assert!(warnings[0].emitted);
+    assert!(warnings[0]
+        .message
+        .contains("The TOML spec requires newlines after table definitions"));
+}
@@ -2114,7 +2114,7 @@ fn registry_index_rejected() {
         .with_status(101)
         .with_stderr(
             "\
-[ERROR] failed to parse manifest at `[..]/foo/Cargo.toml`
+[ERROR] failed to parse manifest at `[..]/foo/Cargo.toml:7:13`
 
 Caused by:
   the `registry.index` config value is no longer supported
@@ -0,0 +1,114 @@
+//! Tests for publishing a `git` dependency with a different requirement, the
+//! `registry-version` unstable feature.
+
+use cargo_test_support::{basic_manifest, git, project};
+
+#[cargo_test]
+fn feature_gated() {
+    let git_project = git::new("bar", |project| {
+        project
+            .file("Cargo.toml", &basic_manifest("bar", "1.0.0"))
+            .file("src/lib.rs", "")
+    });
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.0.1"
+
+                    [dependencies]
+                    bar = {{ git = "{}", version = "1.0", registry-version = "1.0" }}
+                "#,
+                git_project.url()
+            ),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  feature `registry-version` is required
+
+  consider adding `cargo-features = [\"registry-version\"]` to the manifest
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn warns_on_incompatible_zero_versions() {
+    let git_project = git::new("bar", |project| {
+        project
+            .file("Cargo.toml", &basic_manifest("bar", "0.2.1"))
+            .file("src/lib.rs", "")
+    });
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    cargo-features = ["registry-version"]
+
+                    [package]
+                    name = "foo"
+                    version = "0.0.1"
+
+                    [dependencies]
+                    bar = {{ git = "{}", version = "0.2", registry-version = "0.3" }}
+                "#,
+                git_project.url()
+            ),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_stderr_contains(
+            "[WARNING] dependency (bar) requires `0.2` from git, but `0.3` when published; \
+             the versions are not semver compatible",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn compatible_versions_do_not_warn() {
+    let git_project = git::new("bar", |project| {
+        project
+            .file("Cargo.toml", &basic_manifest("bar", "0.2.1"))
+            .file("src/lib.rs", "")
+    });
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    cargo-features = ["registry-version"]
+
+                    [package]
+                    name = "foo"
+                    version = "0.0.1"
+
+                    [dependencies]
+                    bar = {{ git = "{}", version = "0.2.1", registry-version = "0.2" }}
+                "#,
+                git_project.url()
+            ),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_stderr_does_not_contain("[WARNING] [..]semver compatible")
+        .run();
+}
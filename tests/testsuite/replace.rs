@@ -1200,3 +1200,73 @@ fn override_plus_dep() {
         .with_stderr_contains("error: cyclic package dependency: [..]")
         .run();
 }
+
+#[cargo_test]
+fn replace_and_patch_in_virtual_manifest() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [workspace]
+                members = ["foo"]
+
+                [replace]
+                'bar:0.1.0' = { path = "bar" }
+
+                [patch.crates-io]
+                bar = { path = "bar" }
+            "#,
+        )
+        .file("foo/Cargo.toml", &basic_manifest("foo", "0.0.1"))
+        .file("foo/src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml`
+
+Caused by:
+  cannot specify both [replace] and [patch]
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn replace_and_patch_in_package() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+
+                [replace]
+                'bar:0.1.0' = { path = "bar" }
+
+                [patch.crates-io]
+                bar = { path = "bar" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[CWD]/Cargo.toml`
+
+Caused by:
+  cannot specify both [replace] and [patch]
+",
+        )
+        .run();
+}
@@ -1139,6 +1139,8 @@ fn run_default() {
         .with_status(101)
         .with_stderr(
             "\
+[WARNING] `default = []` was found in [features]. \
+An empty `default` feature has no effect and can be removed.
 error: target `foo` in package `foo` requires the features: `a`
 Consider enabling them by passing, e.g., `--features=\"a\"`
 ",
@@ -79,6 +79,7 @@ fn simple() {
                     "incremental": false,
                     "panic": "unwind",
                     "strip": "none",
+                    "self_profile": false,
                     "split_debuginfo": "{...}"
                   },
                   "platform": null,
@@ -123,6 +124,7 @@ fn simple() {
                     "incremental": false,
                     "panic": "unwind",
                     "strip": "none",
+                    "self_profile": false,
                     "split_debuginfo": "{...}"
                   },
                   "platform": null,
@@ -167,6 +169,7 @@ fn simple() {
                     "incremental": false,
                     "panic": "unwind",
                     "strip": "none",
+                    "self_profile": false,
                     "split_debuginfo": "{...}"
                   },
                   "platform": null,
@@ -204,6 +207,7 @@ fn simple() {
                     "incremental": false,
                     "panic": "unwind",
                     "strip": "none",
+                    "self_profile": false,
                     "split_debuginfo": "{...}"
                   },
                   "platform": null,
@@ -444,6 +444,7 @@ fn update_precise_first_run() {
           "req": "^0.2",
           "source": "registry+https://github.com/rust-lang/crates.io-index",
           "target": null,
+          "public": false,
           "uses_default_features": true
         }
       ],
@@ -548,9 +548,10 @@ fn dangling_member() {
         .with_status(101)
         .with_stderr(
             "\
-error: package `[..]` is a member of the wrong workspace
-expected: [..]
-actual: [..]
+[ERROR] `package.workspace` pointers form a cycle:
+  [..]/foo/bar/Cargo.toml
+  -> [..]/foo/baz/Cargo.toml
+  -> [..]/foo/baz/Cargo.toml
 ",
         )
         .run();
@@ -586,7 +587,112 @@ fn cycle() {
     p.cargo("build")
         .with_status(101)
         .with_stderr(
-            "[ERROR] root of a workspace inferred but wasn't a root: [..]/foo/bar/Cargo.toml",
+            "\
+[ERROR] `package.workspace` pointers form a cycle:
+  [..]/foo/Cargo.toml
+  -> [..]/foo/bar/Cargo.toml
+  -> [..]/foo/Cargo.toml
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn workspace_pointer_chain() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [workspace]
+                members = ["a", "b", "c"]
+            "#,
+        )
+        .file(
+            "a/Cargo.toml",
+            r#"
+                [package]
+                name = "a"
+                version = "0.1.0"
+                workspace = "../b"
+            "#,
+        )
+        .file("a/src/lib.rs", "")
+        .file(
+            "b/Cargo.toml",
+            r#"
+                [package]
+                name = "b"
+                version = "0.1.0"
+                workspace = "../c"
+            "#,
+        )
+        .file("b/src/lib.rs", "")
+        .file(
+            "c/Cargo.toml",
+            r#"
+                [package]
+                name = "c"
+                version = "0.1.0"
+                workspace = ".."
+            "#,
+        )
+        .file("c/src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .cwd("a")
+        .with_stderr(
+            "\
+[COMPILING] a v0.1.0 ([..])
+[FINISHED] [..]
+",
+        )
+        .run();
+    assert!(p.root().join("target").is_dir());
+    assert!(!p.root().join("a/target").is_dir());
+}
+
+#[cargo_test]
+fn workspace_pointer_chain_too_long() {
+    let mut p = project().file("Cargo.toml", "[workspace]");
+    for i in 0..12 {
+        p = p
+            .file(
+                &format!("p{}/Cargo.toml", i),
+                &format!(
+                    r#"
+                        [package]
+                        name = "p{}"
+                        version = "0.1.0"
+                        workspace = "../p{}"
+                    "#,
+                    i,
+                    i + 1
+                ),
+            )
+            .file(&format!("p{}/src/lib.rs", i), "");
+    }
+    let p = p.build();
+
+    p.cargo("build")
+        .cwd("p0")
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] followed more than 10 `package.workspace` pointers without reaching a workspace root:
+  [..]/foo/p0/Cargo.toml
+  -> [..]/foo/p1/Cargo.toml
+  -> [..]/foo/p2/Cargo.toml
+  -> [..]/foo/p3/Cargo.toml
+  -> [..]/foo/p4/Cargo.toml
+  -> [..]/foo/p5/Cargo.toml
+  -> [..]/foo/p6/Cargo.toml
+  -> [..]/foo/p7/Cargo.toml
+  -> [..]/foo/p8/Cargo.toml
+  -> [..]/foo/p9/Cargo.toml
+  -> [..]/foo/p10/Cargo.toml
+  -> [..]/foo/p11/Cargo.toml
+",
         )
         .run();
 }
@@ -1061,7 +1167,7 @@ fn new_warning_with_corrupt_ws() {
 failed to parse manifest at `[..]foo/Cargo.toml`
 
 Caused by:
-  could not parse input as TOML
+  could not parse input as TOML at `[..]foo/Cargo.toml:1:5`
 
 Caused by:
   expected an equals, found eof at line 1 column 5
@@ -1558,6 +1664,44 @@ fn excluded_simple() {
     assert!(p.root().join("foo/target").is_dir());
 }
 
+#[cargo_test]
+fn excluded_double_star_glob() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [project]
+                name = "ws"
+                version = "0.1.0"
+                authors = []
+
+                [workspace]
+                exclude = ["external/**"]
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("external/a/Cargo.toml", &basic_manifest("a", "0.1.0"))
+        .file("external/a/src/lib.rs", "")
+        .file("external/b/c/Cargo.toml", &basic_manifest("c", "0.1.0"))
+        .file("external/b/c/src/lib.rs", "")
+        .file(
+            "externally/Cargo.toml",
+            &basic_manifest("externally", "0.1.0"),
+        )
+        .file("externally/src/lib.rs", "");
+    let p = p.build();
+
+    p.cargo("build").cwd("external/a").run();
+    assert!(p.root().join("external/a/target").is_dir());
+    p.cargo("build").cwd("external/b/c").run();
+    assert!(p.root().join("external/b/c/target").is_dir());
+    p.cargo("build")
+        .cwd("externally")
+        .with_status(101)
+        .with_stderr_contains("[ERROR] current package believes it's in a workspace when it's not:")
+        .run();
+}
+
 #[cargo_test]
 fn exclude_members_preferred() {
     let p = project()
@@ -2396,3 +2540,285 @@ fn virtual_primary_package_env_var() {
     p.cargo("clean").run();
     p.cargo("test -p foo").run();
 }
+
+#[cargo_test]
+fn standalone_gated() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [project]
+                name = "foo"
+                version = "0.1.0"
+                standalone = true
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] failed to parse manifest at `[..]`
+
+Caused by:
+  feature `standalone-package` is required
+
+  consider adding `cargo-features = [\"standalone-package\"]` to the manifest
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn standalone_skips_root_discovery() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [project]
+                name = "ws"
+                version = "0.1.0"
+                authors = []
+
+                [workspace]
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            "foo/Cargo.toml",
+            r#"
+                cargo-features = ["standalone-package"]
+
+                [project]
+                name = "foo"
+                version = "0.1.0"
+                authors = []
+                standalone = true
+            "#,
+        )
+        .file("foo/src/lib.rs", "");
+    let p = p.build();
+
+    p.cargo("build")
+        .cwd("foo")
+        .masquerade_as_nightly_cargo()
+        .run();
+    assert!(p.root().join("foo/target").is_dir());
+    assert!(!p.root().join("target").is_dir());
+}
+
+#[cargo_test]
+fn standalone_path_dep_is_not_member() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [project]
+                name = "ws"
+                version = "0.1.0"
+                authors = []
+
+                [dependencies]
+                foo = { path = "foo" }
+
+                [workspace]
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            "foo/Cargo.toml",
+            r#"
+                cargo-features = ["standalone-package"]
+
+                [project]
+                name = "foo"
+                version = "0.1.0"
+                authors = []
+                standalone = true
+            "#,
+        )
+        .file("foo/src/lib.rs", "");
+    let p = p.build();
+
+    p.cargo("build --workspace")
+        .masquerade_as_nightly_cargo()
+        .with_stderr(
+            "\
+[COMPILING] foo v0.1.0 ([CWD]/foo)
+[COMPILING] ws v0.1.0 ([CWD])
+[FINISHED] [..]
+",
+        )
+        .run();
+    assert!(!p.root().join("foo/Cargo.lock").is_file());
+    p.cargo("build")
+        .cwd("foo")
+        .masquerade_as_nightly_cargo()
+        .run();
+    assert!(p.root().join("foo/Cargo.lock").is_file());
+}
+
+#[cargo_test]
+fn standalone_listed_in_members() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [workspace]
+                members = ["foo"]
+            "#,
+        )
+        .file(
+            "foo/Cargo.toml",
+            r#"
+                cargo-features = ["standalone-package"]
+
+                [project]
+                name = "foo"
+                version = "0.1.0"
+                authors = []
+                standalone = true
+            "#,
+        )
+        .file("foo/src/lib.rs", "");
+    let p = p.build();
+
+    p.cargo("build")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] package `[CWD]/foo/Cargo.toml` is listed in `workspace.members` of the \
+manifest located at: [CWD]/Cargo.toml
+but it sets `package.standalone = true`, which opts it out of every workspace; \
+remove it from `workspace.members` or remove `package.standalone`
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn workspace_limits() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["workspace-limits"]
+
+                [workspace]
+                members = ["a", "b", "c"]
+
+                [workspace.limits]
+                max-direct-dependencies = 2
+                max-features = 2
+            "#,
+        )
+        .file(
+            "a/Cargo.toml",
+            r#"
+                [package]
+                name = "a"
+                version = "0.1.0"
+
+                [dependencies]
+                b = { path = "../b" }
+
+                [target.'cfg(unix)'.dependencies]
+                b = { path = "../b" }
+
+                [build-dependencies]
+                c = { path = "../c" }
+
+                [features]
+                one = []
+                two = []
+            "#,
+        )
+        .file("a/src/lib.rs", "")
+        .file("b/Cargo.toml", &basic_manifest("b", "0.1.0"))
+        .file("b/src/lib.rs", "")
+        .file("c/Cargo.toml", &basic_manifest("c", "0.1.0"))
+        .file("c/src/lib.rs", "")
+        .build();
+
+    // Dependencies are counted once per name, so `a` is within its budget.
+    p.cargo("metadata --format-version 1")
+        .masquerade_as_nightly_cargo()
+        .with_stderr("")
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            cargo-features = ["workspace-limits"]
+
+            [workspace]
+            members = ["a", "b", "c"]
+
+            [workspace.limits]
+            max-direct-dependencies = 1
+            max-features = 1
+        "#,
+    );
+    p.cargo("metadata --format-version 1")
+        .masquerade_as_nightly_cargo()
+        .with_stderr(
+            "\
+[WARNING] package `a` has 2 direct dependencies, more than the limit of 1 set by \
+`workspace.limits.max-direct-dependencies`
+package: [..]/foo/a/Cargo.toml
+[WARNING] package `a` has 2 features, more than the limit of 1 set by \
+`workspace.limits.max-features`
+package: [..]/foo/a/Cargo.toml
+",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn workspace_limits_deny() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["workspace-limits"]
+
+                [workspace]
+                members = ["a", "b"]
+
+                [workspace.limits]
+                max-dev-dependencies = 0
+                deny = true
+            "#,
+        )
+        .file(
+            "a/Cargo.toml",
+            r#"
+                [package]
+                name = "a"
+                version = "0.1.0"
+
+                [dev-dependencies]
+                b = { path = "../b" }
+            "#,
+        )
+        .file("a/src/lib.rs", "")
+        .file("b/Cargo.toml", &basic_manifest("b", "0.1.0"))
+        .file("b/src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo()
+        .with_status(101)
+        .with_stderr(
+            "\
+[ERROR] package `a` has 1 dev-dependencies, more than the limit of 0 set by \
+`workspace.limits.max-dev-dependencies`
+package: [..]/foo/a/Cargo.toml
+",
+        )
+        .run();
+}